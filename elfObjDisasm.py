@@ -21,6 +21,9 @@ def elfObjDisasmMain():
 
     parser.add_argument("--data-output", help="Path to output the data and rodata disassembly")
 
+    parser.add_argument("--mdebug-symbols", help="Import the functions and variables described by the `.mdebug` section, including their sizes and types, if the elf has one. Defaults to False", action=spimdisasm.common.Utils.BooleanOptionalAction)
+    parser.add_argument("--sanitize-symbol-names", help="Replace the characters the assembler doesn't accept on symbol names with reversible escape sequences. Defaults to False", action=spimdisasm.common.Utils.BooleanOptionalAction)
+
     spimdisasm.common.GlobalConfig.addParametersToArgParse(parser)
 
     spimdisasm.mips.InstructionConfig.addParametersToArgParse(parser)
@@ -41,11 +44,20 @@ def elfObjDisasmMain():
 
     context = spimdisasm.common.Context()
     context.globalSegment.changeRanges(0x0, 0xFFFFFFFF, 0x0, 0xFFFFFFFF)
+    if args.sanitize_symbol_names:
+        context.symbolNameSanitizer = spimdisasm.common.SymbolNameSanitizer()
 
     array_of_bytes = spimdisasm.common.Utils.readFileAsBytearray(args.binary)
 
     elfFile = spimdisasm.elf32.Elf32File(array_of_bytes)
 
+    if args.mdebug_symbols:
+        if elfFile.mdebug is not None:
+            symbolsCount = elfFile.mdebug.seedContext(context)
+            spimdisasm.common.Utils.printVerbose(f"Imported {symbolsCount} symbols from .mdebug")
+        else:
+            spimdisasm.common.Utils.epprintQuietless("Warning: --mdebug-symbols was passed, but the elf doesn't have a .mdebug section")
+
     processedFiles: dict[spimdisasm.common.FileSectionType, tuple[pathlib.Path, spimdisasm.mips.sections.SectionBase]] = dict()
 
     textOutput = args.output
@@ -79,6 +91,15 @@ def elfObjDisasmMain():
 
         processedFiles[spimdisasm.common.FileSectionType.Bss] = (outputFilePath, spimdisasm.mips.sections.SectionBss(context, 0, 0, 0, elfFile.nobits, inputPath.stem, 0, None))
 
+    for sectionType, sectionBytes in elfFile.mipsSpecialSections.items():
+        outputPath = dataOutput
+
+        outputFilePath = pathlib.Path(dataOutput)
+        if outputPath != "-":
+            outputFilePath /= inputPath.stem
+
+        processedFiles[sectionType] = (outputFilePath, spimdisasm.mips.sections.SectionPassthrough(context, 0, 0, 0, inputPath.stem, sectionBytes, sectionType, 0, None))
+
     if elfFile.symtab is not None and elfFile.strtab is not None:
         # Inject symbols from the reloc table referenced in each section
         for sectType, relocs in elfFile.rel.items():
@@ -102,7 +123,7 @@ def elfObjDisasmMain():
                 continue
             sectName = elfFile.shstrtab[sectHeaderEntry.name]
             sectType = spimdisasm.common.FileSectionType.fromStr(sectName)
-            if sectType != spimdisasm.common.FileSectionType.Invalid:
+            if sectType in spimdisasm.common.FileSections_ListBasic:
                 subSection = processedFiles[sectType][1]
                 symName = elfFile.strtab[symEntry.name]
 
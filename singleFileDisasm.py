@@ -42,6 +42,8 @@ def disassemblerMain():
     parser.add_argument("--nuke-pointers", help="Use every technique available to remove pointers", action=spimdisasm.common.Utils.BooleanOptionalAction)
     parser.add_argument("--ignore-words", help="A space separated list of hex numbers. Any word differences which starts in any of the provided arguments will be ignored. Max value: FF. Only works when --nuke-pointers is passed", action="extend", nargs="+")
 
+    parser.add_argument("--write-macro-inc", help="Writes the definitions of the label macros used by the generated assembly to the given path, so it can be included by the generated files", metavar="PATH")
+
     parser.add_argument("--write-binary", help=f"Produce a binary from the processed file. Defaults to {spimdisasm.common.GlobalConfig.WRITE_BINARY}", action=spimdisasm.common.Utils.BooleanOptionalAction)
 
 
@@ -74,7 +76,6 @@ def disassemblerMain():
     context = spimdisasm.common.Context()
     context.parseArgs(args)
 
-    array_of_bytes = spimdisasm.common.Utils.readFileAsBytearray(args.binary)
     input_name = os.path.splitext(os.path.split(args.binary)[1])[0]
 
     processedFiles = {
@@ -112,44 +113,46 @@ def disassemblerMain():
     if dataOutput is None:
         dataOutput = textOutput
 
-    highestVromEnd = len(array_of_bytes)
-    highestVramEnd = 0x80000000 + highestVromEnd
-    fileVram = int(args.vram, 16)
-    if fileVram != 0:
-        highestVramEnd = (fileVram & 0xF0000000) + highestVromEnd
-
-    for row in splits:
-        if row.section == spimdisasm.common.FileSectionType.Text:
-            outputPath = textOutput
-        elif row.section == spimdisasm.common.FileSectionType.Data:
-            outputPath = dataOutput
-        elif row.section == spimdisasm.common.FileSectionType.Rodata:
-            outputPath = dataOutput
-        elif row.section == spimdisasm.common.FileSectionType.Bss:
-            outputPath = dataOutput
-        else:
-            spimdisasm.common.Utils.eprint("Error! Section not set!")
-            exit(1)
-
-        outputFilePath = outputPath
-        if outputPath != "-":
-            fileName = row.fileName
-            if row.fileName == "":
-                fileName = f"{input_name}_{row.vram:08X}"
-
-            outputFilePath = os.path.join(outputPath, fileName)
-
-        vramEnd = row.vram + row.nextOffset - row.offset
-        if vramEnd > highestVramEnd:
-            highestVramEnd = vramEnd
-        if row.nextOffset > highestVromEnd:
-            highestVromEnd = row.nextOffset
-
-        spimdisasm.common.Utils.printVerbose(f"Reading '{row.fileName}'")
-        f = spimdisasm.mips.FilesHandlers.createSectionFromSplitEntry(row, array_of_bytes, outputFilePath, context)
-        f.setCommentOffset(row.offset)
-        processedFiles[row.section].append(f)
-        processedFilesOutputPaths[row.section].append(outputFilePath)
+    # Sections copy their own words, so the mapping is only needed while creating them
+    with spimdisasm.common.Utils.mapFile(args.binary) as array_of_bytes:
+        highestVromEnd = len(array_of_bytes)
+        highestVramEnd = 0x80000000 + highestVromEnd
+        fileVram = int(args.vram, 16)
+        if fileVram != 0:
+            highestVramEnd = (fileVram & 0xF0000000) + highestVromEnd
+
+        for row in splits:
+            if row.section == spimdisasm.common.FileSectionType.Text:
+                outputPath = textOutput
+            elif row.section == spimdisasm.common.FileSectionType.Data:
+                outputPath = dataOutput
+            elif row.section == spimdisasm.common.FileSectionType.Rodata:
+                outputPath = dataOutput
+            elif row.section == spimdisasm.common.FileSectionType.Bss:
+                outputPath = dataOutput
+            else:
+                spimdisasm.common.Utils.eprint("Error! Section not set!")
+                exit(1)
+
+            outputFilePath = outputPath
+            if outputPath != "-":
+                fileName = row.fileName
+                if row.fileName == "":
+                    fileName = f"{input_name}_{row.vram:08X}"
+
+                outputFilePath = os.path.join(outputPath, fileName)
+
+            vramEnd = row.vram + row.nextOffset - row.offset
+            if vramEnd > highestVramEnd:
+                highestVramEnd = vramEnd
+            if row.nextOffset > highestVromEnd:
+                highestVromEnd = row.nextOffset
+
+            spimdisasm.common.Utils.printVerbose(f"Reading '{row.fileName}'")
+            f = spimdisasm.mips.FilesHandlers.createSectionFromSplitEntry(row, array_of_bytes, outputFilePath, context)
+            f.setCommentOffset(row.offset)
+            processedFiles[row.section].append(f)
+            processedFilesOutputPaths[row.section].append(outputFilePath)
 
     context.globalSegment.changeRanges(0, highestVromEnd, 0x80000000, highestVramEnd)
 
@@ -209,6 +212,10 @@ def disassemblerMain():
     if args.split_functions is not None:
         spimdisasm.common.Utils.printVerbose("\nSpliting functions...")
         funcTotal = sum(len(x.symbolList) for x in processedFiles[spimdisasm.common.FileSectionType.Text])
+        allFuncs = [func for f in processedFiles[spimdisasm.common.FileSectionType.Text] for func in f.symbolList if isinstance(func, spimdisasm.mips.symbols.SymbolFunction)]
+        for pairingError in spimdisasm.mips.FilesHandlers.getAmbiguousRodataPairings(allFuncs, processedFiles[spimdisasm.common.FileSectionType.Rodata]):
+            spimdisasm.common.Utils.epprintQuietless(f"Warning: {pairingError}")
+        forcedMigrations = spimdisasm.mips.FilesHandlers.getForcedRodataMigrations(processedFiles[spimdisasm.common.FileSectionType.Rodata])
         i = 0
         for f in processedFiles[spimdisasm.common.FileSectionType.Text]:
             for func in f.symbolList:
@@ -221,7 +228,7 @@ def disassemblerMain():
 
 
                 assert isinstance(func, spimdisasm.mips.symbols.SymbolFunction)
-                spimdisasm.mips.FilesHandlers.writeSplitedFunction(os.path.join(args.split_functions, f.name), func, processedFiles[spimdisasm.common.FileSectionType.Rodata])
+                spimdisasm.mips.FilesHandlers.writeSplitedFunction(os.path.join(args.split_functions, f.name), func, processedFiles[spimdisasm.common.FileSectionType.Rodata], forcedMigrations)
 
                 i += 1
         spimdisasm.mips.FilesHandlers.writeOtherRodata(args.split_functions, processedFiles[spimdisasm.common.FileSectionType.Rodata])
@@ -231,6 +238,33 @@ def disassemblerMain():
         contextPath.parent.mkdir(parents=True, exist_ok=True)
         context.saveContextToFile(contextPath)
 
+    if args.save_symbols_json is not None:
+        symbolsJsonPath = Path(args.save_symbols_json)
+        symbolsJsonPath.parent.mkdir(parents=True, exist_ok=True)
+        with symbolsJsonPath.open("w") as f:
+            context.exportSymbolsJson(f)
+
+    if args.save_splat_symbols is not None:
+        splatSymbolsPath = Path(args.save_splat_symbols)
+        splatSymbolsPath.parent.mkdir(parents=True, exist_ok=True)
+        with splatSymbolsPath.open("w") as f:
+            context.exportSplatSymbols(f)
+
+    if args.save_type_inference is not None:
+        typeInferencePath = Path(args.save_type_inference)
+        typeInferencePath.parent.mkdir(parents=True, exist_ok=True)
+        with typeInferencePath.open("w") as f:
+            context.saveTypeInferenceReport(f)
+
+    if args.save_string_encodings is not None:
+        stringEncodingsPath = Path(args.save_string_encodings)
+        stringEncodingsPath.parent.mkdir(parents=True, exist_ok=True)
+        with stringEncodingsPath.open("w") as f:
+            context.saveStringEncodingsReport(f)
+
+    if args.write_macro_inc is not None:
+        spimdisasm.mips.FilesHandlers.writeMacroInc(args.write_macro_inc)
+
     spimdisasm.common.Utils.printQuietless(lenLastLine*" " + "\r", end="")
     spimdisasm.common.Utils.printQuietless(f"Done: {args.binary}")
 
@@ -6,12 +6,48 @@
 from __future__ import annotations
 
 import argparse
+import dataclasses
+import json
 from pathlib import Path
+from typing import Callable, Generator, Iterable, TextIO
 
 from . import Utils
 from .FileSectionType import FileSectionType
-from .ContextSymbols import SymbolSpecialType, ContextOffsetSymbol, ContextRelocSymbol
+from .ContextSymbols import SymbolSpecialType, SymbolOrigin, ContextSymbol, ContextOffsetSymbol, ContextRelocSymbol
+from .GlobalConfig import GlobalConfig, InputEndian
+from .ParentSegmentInfo import ParentSegmentInfo
+from .SortedDict import SortedDict
 from .SymbolsSegment import SymbolsSegment
+from .SymbolNameSanitizer import SymbolNameSanitizer
+
+
+@dataclasses.dataclass
+class InferredType:
+    "A type guessed by the analysis for an autogenerated symbol, with the evidence backing it"
+
+    vram: int
+    name: str
+    type: str
+    evidence: list[str]
+    confidence: float
+    "Between 0 and 1. Low values mean the guess is weak or contradicted by other evidence"
+
+    BASE_CONFIDENCE = 0.5
+    "Confidence of a guess before looking at any evidence"
+    GUESSER_PENALTY = 0.1
+    "The type comes from the string or double guessers instead of the instructions"
+    SINGLE_ACCESS_TYPE_BONUS = 0.3
+    "Every instruction accessing the symbol agrees with the guessed type"
+    MIXED_ACCESS_TYPES_PENALTY = 0.3
+    "The symbol is accessed as different types, or as a type other than the guessed one"
+    ALIGNED_BONUS = 0.1
+    "The symbol is aligned to the size of the guessed type"
+    UNALIGNED_PENALTY = 0.3
+    "The symbol is not aligned to the size of the guessed type"
+    REFERENCED_BONUS = 0.1
+    "Something else references the symbol"
+    UNREFERENCED_PENALTY = 0.2
+    "Nothing references the symbol, so the guess is based on the data alone"
 
 
 class Context:
@@ -19,15 +55,36 @@ class Context:
 
     def __init__(self):
         # Arbitrary initial range
-        self.globalSegment = SymbolsSegment(0x0, 0x1000, 0x80000000, 0x80001000, overlayCategory=None)
+        self.globalSegment = SymbolsSegment(0x0, 0x1000, 0x80000000, 0x80001000, overlayCategory=None, context=self)
         # For symbols that we don't know where they come from
-        self.unknownSegment = SymbolsSegment(None, None, 0x00000000, 0xFFFFFFFF, overlayCategory=None)
+        self.unknownSegment = SymbolsSegment(None, None, 0x00000000, 0xFFFFFFFF, overlayCategory=None, context=self)
 
         self.overlaySegments: dict[str, dict[int, SymbolsSegment]] = dict()
         "Outer key is overlay type, inner key is the vrom of the overlay's segment"
 
+        self.overlayCategoriesPriority: list[str] = list()
+        "Order in which other overlay categories are searched when resolving a reference from an overlay. Categories not listed here are searched afterwards, in the order they were added"
+
         # Stuff that looks like pointers, but the disassembler shouldn't count it as a pointer
         self.bannedSymbols: set[int] = set()
+        self.ignoredRanges: list[tuple[int, int]] = list()
+        "[`start`, `end`) vram ranges handled elsewhere, like by another tool. Symbols are never created inside them and references to them are kept as raw addresses"
+
+        self.stringArgumentFunctions: dict[int, set[int]] = dict()
+        """Maps the vram of functions which take strings (like `osSyncPrintf`) to the indices of their arguments passed on `$a0`-`$a3` which are strings.
+        The symbols whose addresses are passed on those arguments are typed as strings"""
+
+        self.referenceFilter: Callable[[int, int, str], bool]|None = None
+        """Called before the analysis symbolizes a reference, with the vram of the instruction or word making the reference, the referenced vram and the name
+        of the relocation type the reference would use (like `R_MIPS_HI16` or `R_MIPS_32`). Returning `False` vetoes the reference, which is kept as a raw value"""
+
+        self.autogeneratedNamesCallback: Callable[[int, SymbolSpecialType|str|None, int|None, ParentSegmentInfo], str|None]|None = None
+        """Called when a name is generated for a symbol which wasn't given one, with the vram, type and vrom (if known) of the symbol and the segment it belongs to.
+        If it returns `None`, or if no callback is set, then the default name (like `func_80012345` or `D_80012345`) is used instead"""
+
+        self.symbolNameSanitizer: SymbolNameSanitizer|None = None
+        """If set, the names the assembler doesn't accept (like the ones containing `$` or `.`) are escaped with it when symbols are emitted.
+        The original names are kept in the context, and can be recovered with `symbolNameSanitizer.unsanitize`"""
 
         # First key is the section type, sub key is offset relative to the start of that section
         self.offsetSymbols: dict[FileSectionType, dict[int, ContextOffsetSymbol]] = {
@@ -49,11 +106,44 @@ class Context:
         # The addresses every jump table has
         self.offsetJumpTablesLabels: dict[int, ContextOffsetSymbol] = dict()
 
+        self.breakCodesComments: dict[int, str] = dict()
+        "Comments emitted after `break` instructions, keyed by the code of the instruction (like `7` for `break 0x7`)"
+        self.syscallCodesComments: dict[int, str] = dict()
+        "Comments emitted after `syscall` instructions, keyed by the code of the instruction"
+
+
+    def addOverlaySegment(self, overlayCategory: str, segmentVromStart: int, segmentVromEnd: int, segmentVramStart: int, segmentVramEnd: int, endian: InputEndian|None=None, isolated: bool=False) -> None:
+        """Registers a new overlay segment.
 
-    def addOverlaySegment(self, overlayCategory: str, segmentVromStart: int, segmentVromEnd: int, segmentVramStart: int, segmentVramEnd: int) -> None:
+        Segments which share the same rom (for example buffers loaded at different addresses) must be put in different overlay categories,
+        so each one keeps its own symbols, including the ones of its bss.
+
+        If `isolated` is `True` then references from this segment are only resolved to its own symbols or the ones of the global segment."""
+        if segmentVromStart in self.overlaySegments.get(overlayCategory, dict()):
+            raise RuntimeError(f"An overlay segment starting at vrom 0x{segmentVromStart:06X} already exists in the overlay category '{overlayCategory}'. Use a different overlay category for segments that share the same rom")
         if overlayCategory not in self.overlaySegments:
             self.overlaySegments[overlayCategory] = dict()
-        self.overlaySegments[overlayCategory][segmentVromStart] = SymbolsSegment(segmentVromStart, segmentVromEnd, segmentVramStart, segmentVramEnd, overlayCategory=overlayCategory)
+        self.overlaySegments[overlayCategory][segmentVromStart] = SymbolsSegment(segmentVromStart, segmentVromEnd, segmentVramStart, segmentVramEnd, overlayCategory=overlayCategory, endian=endian, isolated=isolated, context=self)
+
+
+    def getOverlayCategoriesBySearchOrder(self) -> list[str]:
+        "Returns every overlay category, sorted by `overlayCategoriesPriority`"
+        categories = [category for category in self.overlayCategoriesPriority if category in self.overlaySegments]
+        for category in self.overlaySegments:
+            if category not in categories:
+                categories.append(category)
+        return categories
+
+
+    def getSymbolsInRange(self, vramStart: int, vramEnd: int, includeOverlapping: bool=False, overlayCategory: str|None=None, segmentVromStart: int|None=None) -> Generator[ContextSymbol, None, None]:
+        """Iterates the symbols which start in the [`vramStart`, `vramEnd`) range, sorted by address.
+
+        The global segment is used unless both `overlayCategory` and `segmentVromStart` are passed, in which case that overlay segment is used instead.
+        See `SymbolsSegment.getSymbolsInRange` for the meaning of `includeOverlapping`."""
+        segment = self.globalSegment
+        if overlayCategory is not None and segmentVromStart is not None:
+            segment = self.overlaySegments[overlayCategory][segmentVromStart]
+        return segment.getSymbolsInRange(vramStart, vramEnd, includeOverlapping=includeOverlapping)
 
 
     def getOffsetSymbol(self, offset: int, sectionType: FileSectionType) -> ContextOffsetSymbol|None:
@@ -107,6 +197,63 @@ class Context:
     def fillDefaultBannedSymbols(self):
         self.bannedSymbols |= self.N64DefaultBanned
 
+    def addIgnoredRanges(self, ranges: Iterable[tuple[int, int]]) -> None:
+        "Adds every [`start`, `end`) vram range of `ranges` to `ignoredRanges`"
+        for start, end in ranges:
+            if start >= end:
+                raise RuntimeError(f"Invalid ignored range [0x{start:08X}, 0x{end:08X})")
+            self.ignoredRanges.append((start, end))
+
+    def readIgnoredRangesCsv(self, filepath: Path):
+        "Reads a csv where each row is the hexadecimal `start,end` vram of an ignored range"
+        if not filepath.exists():
+            return
+
+        ranges: list[tuple[int, int]] = list()
+        for row in Utils.readCsv(str(filepath)):
+            if len(row) == 0:
+                continue
+
+            startStr, endStr = row
+            ranges.append((int(startStr, 16), int(endStr, 16)))
+        self.addIgnoredRanges(ranges)
+
+    def addStringArgumentFunction(self, funcVram: int, argumentIndex: int=0) -> None:
+        "Marks the argument `argumentIndex` (from `0` for `$a0` to `3` for `$a3`) of the function at `funcVram` as a string. See `stringArgumentFunctions`"
+        if not (0 <= argumentIndex < 4):
+            raise RuntimeError(f"Only the arguments passed on registers can be marked as strings, got argument {argumentIndex} of the function at 0x{funcVram:08X}")
+        if funcVram not in self.stringArgumentFunctions:
+            self.stringArgumentFunctions[funcVram] = set()
+        self.stringArgumentFunctions[funcVram].add(argumentIndex)
+
+    def readStringArgumentFunctionsCsv(self, filepath: Path):
+        "Reads a csv where each row is the hexadecimal vram of a function which takes strings and the index of the string argument"
+        if not filepath.exists():
+            return
+
+        for row in Utils.readCsv(str(filepath)):
+            if len(row) == 0:
+                continue
+
+            vramStr, argumentIndexStr = row
+            self.addStringArgumentFunction(int(vramStr, 16), int(argumentIndexStr, 0))
+
+    def isAddressIgnored(self, address: int) -> bool:
+        for start, end in self.ignoredRanges:
+            if start <= address < end:
+                return True
+        return False
+
+    def shouldCreateReference(self, fromVram: int, toVram: int, relocTypeName: str) -> bool:
+        "Checks if `referenceFilter` allows the reference from `fromVram` to `toVram`. Every reference is allowed if there's no filter"
+        if self.referenceFilter is None:
+            return True
+        return self.referenceFilter(fromVram, toVram, relocTypeName)
+
+    def isAddressBanned(self, address: int) -> bool:
+        "Checks if `address` must not be symbolized, either because it is a banned symbol or because it is inside an ignored range"
+        return address in self.bannedSymbols or self.isAddressIgnored(address)
+
 
     def saveContextToFile(self, contextPath: Path):
         with contextPath.open("w") as f:
@@ -126,11 +273,365 @@ class Context:
                     overlaySegment.saveContextToFile(f)
 
 
+    def exportSymbolsJson(self, f: TextIO) -> None:
+        """Writes every symbol of the global segment and the overlay segments to `f` as json.
+
+        The schema is an object with a `version` number and a `symbols` list. Each entry contains the `name`, `vram`, `vrom`, `size`,
+        `type`, `arrayCount` and `section` of the symbol, and a `segment` object containing the `overlayCategory` and `vromStart` of the overlay
+        segment it belongs to, or `null` if it belongs to the global segment.
+
+        String symbols which don't use `GlobalConfig.STRING_ENCODING` also have an `encoding` entry, and symbols with an explicit alignment have an `alignment` entry.
+        Strings without a NUL terminator have an `unterminated` entry set to `true`.
+
+        The `size` is the user declared size of the symbol if it has one, or the size determined by the analysis otherwise, so importing
+        this file on a later run makes those sizes stable."""
+        entries: list[dict] = list()
+
+        segments: list[tuple[dict|None, SymbolsSegment]] = [(None, self.globalSegment)]
+        for overlayCategory, segmentsPerVrom in self.overlaySegments.items():
+            for segmentVrom, overlaySegment in segmentsPerVrom.items():
+                segments.append(({"overlayCategory": overlayCategory, "vromStart": segmentVrom}, overlaySegment))
+
+        for segmentInfo, segment in segments:
+            for contextSym in segment.symbols.values():
+                entry = {
+                    "name": contextSym.getUnsanitizedName(),
+                    "vram": contextSym.vram,
+                    "vrom": contextSym.vromAddress,
+                    "size": contextSym.size if contextSym.size is not None else contextSym.autodetectedSize,
+                    "type": contextSym.getType(),
+                    "arrayCount": contextSym.arrayCount,
+                    "section": contextSym.sectionType.toStr(),
+                    "segment": segmentInfo,
+                }
+                if contextSym.encoding is not None:
+                    entry["encoding"] = contextSym.encoding
+                if contextSym.alignment is not None:
+                    entry["alignment"] = contextSym.alignment
+                if contextSym.structLayout is not None:
+                    entry["structLayout"] = [[fieldOffset, fieldType] for fieldOffset, fieldType in contextSym.structLayout]
+                if contextSym.isUnterminatedString:
+                    entry["unterminated"] = True
+                entries.append(entry)
+
+        json.dump({"version": 1, "symbols": entries}, f, indent=4)
+        f.write("\n")
+
+    SplatSpecialTypes: dict[SymbolSpecialType, str] = {
+        SymbolSpecialType.function: "func",
+        SymbolSpecialType.branchlabel: "label",
+        SymbolSpecialType.jumptable: "jtbl",
+        SymbolSpecialType.jumptablelabel: "jtbl_label",
+        SymbolSpecialType.altentry: "label",
+    }
+
+    def exportSplatSymbols(self, f: TextIO) -> None:
+        """Writes every symbol of the global segment and the overlay segments to `f` using the `symbol_addrs.txt` format of splat.
+
+        Each line has the form `name = 0xVRAM; // type:TYPE size:0xSIZE rom:0xROM`, where every hint is only emitted if it is known.
+        Constants and hardware registers are not exported"""
+        segments: list[SymbolsSegment] = [self.globalSegment]
+        for segmentsPerVrom in self.overlaySegments.values():
+            segments.extend(segmentsPerVrom.values())
+
+        for segment in segments:
+            for contextSym in segment.symbols.values():
+                if contextSym.type in {SymbolSpecialType.constant, SymbolSpecialType.hardwarereg}:
+                    continue
+
+                hints: list[str] = list()
+                if isinstance(contextSym.type, SymbolSpecialType):
+                    splatType = self.SplatSpecialTypes.get(contextSym.type)
+                    if splatType is not None:
+                        hints.append(f"type:{splatType}")
+                elif contextSym.isString():
+                    hints.append("type:asciz")
+                elif contextSym.type is not None:
+                    hints.append(f"type:{contextSym.type}")
+
+                size = contextSym.size if contextSym.size is not None else contextSym.autodetectedSize
+                if size is not None:
+                    hints.append(f"size:0x{size:X}")
+                if contextSym.vromAddress is not None:
+                    hints.append(f"rom:0x{contextSym.vromAddress:X}")
+
+                line = f"{contextSym.getName()} = 0x{contextSym.vram:08X};"
+                if len(hints) > 0:
+                    line += " // " + " ".join(hints)
+                f.write(line + "\n")
+
+    def importSymbolsJson(self, f: TextIO, overwrite: bool=False) -> None:
+        """Reads symbols exported by `exportSymbolsJson` and adds them as user declared symbols.
+
+        A RuntimeError is raised if an entry has the same address as an already named symbol but a different name, unless `overwrite` is `True`.
+        Entries of overlay segments require that segment to be already added to the context."""
+        data = json.load(f)
+
+        for entry in data["symbols"]:
+            segment = self.globalSegment
+            segmentInfo = entry.get("segment", None)
+            if segmentInfo is not None:
+                overlayCategory = segmentInfo["overlayCategory"]
+                segmentVromStart = segmentInfo["vromStart"]
+                if segmentVromStart not in self.overlaySegments.get(overlayCategory, dict()):
+                    raise RuntimeError(f"Symbol '{entry['name']}' belongs to an unknown overlay segment ({overlayCategory}, 0x{segmentVromStart:06X})")
+                segment = self.overlaySegments[overlayCategory][segmentVromStart]
+
+            vram = entry["vram"]
+            name = entry["name"]
+            existingSym = segment.getSymbol(vram, tryPlusOffset=False)
+            if existingSym is not None and existingSym.name is not None and existingSym.name != name:
+                if not overwrite:
+                    raise RuntimeError(f"Symbol '{name}' at 0x{vram:08X} collides with already existing symbol '{existingSym.name}'")
+
+            contextSym = segment.addSymbol(vram, sectionType=FileSectionType.fromStr(entry.get("section", "")), vromAddress=entry.get("vrom", None))
+            if contextSym.sectionType == FileSectionType.Invalid:
+                contextSym.sectionType = FileSectionType.Unknown
+            contextSym.name = name
+            contextSym.isUserDeclared = True
+            contextSym.isAutogenerated = False
+
+            size = entry.get("size", None)
+            if size is not None:
+                contextSym.size = size
+
+            arrayCount = entry.get("arrayCount", None)
+            if arrayCount is not None:
+                contextSym.arrayCount = arrayCount
+
+            encoding = entry.get("encoding", None)
+            if encoding is not None:
+                contextSym.encoding = encoding
+
+            alignment = entry.get("alignment", None)
+            if alignment is not None:
+                contextSym.alignment = alignment
+
+            structLayout = entry.get("structLayout", None)
+            if structLayout is not None:
+                contextSym.setStructLayout([(fieldOffset, fieldType) for fieldOffset, fieldType in structLayout])
+
+            if entry.get("unterminated", False):
+                contextSym.isUnterminatedString = True
+
+            symType = entry.get("type", "")
+            if symType:
+                specialType = SymbolSpecialType.fromStr(symType)
+                if specialType is not None:
+                    contextSym.type = specialType
+                else:
+                    contextSym.type = symType
+
+
+    def explainSymbol(self, vram: int, overlayCategory: str|None=None, segmentVromStart: int|None=None) -> tuple[SymbolOrigin, int|None]|None:
+        """Returns why the symbol at `vram` exists, and the vrom of the instruction or word which caused it if known.
+
+        `None` is returned if there's no symbol at that address. Overlay segments are searched if `overlayCategory` and `segmentVromStart` are passed."""
+        segment = self.globalSegment
+        if overlayCategory is not None and segmentVromStart is not None:
+            segment = self.overlaySegments[overlayCategory][segmentVromStart]
+
+        contextSym = segment.getSymbol(vram, tryPlusOffset=False)
+        if contextSym is None:
+            return None
+        if contextSym.isUserDeclared or not contextSym.isAutogenerated:
+            return SymbolOrigin.userDeclared, None
+        if contextSym.origin is None:
+            return SymbolOrigin.analysis, None
+        return contextSym.origin, contextSym.originVrom
+
+    @staticmethod
+    def _isCoalescableDataSymbol(contextSym: ContextSymbol) -> bool:
+        if contextSym.name is not None or contextSym.isUserDeclared or not contextSym.isAutogenerated:
+            return False
+        if isinstance(contextSym.type, SymbolSpecialType):
+            return False
+        return contextSym.sectionType in {FileSectionType.Data, FileSectionType.Rodata, FileSectionType.Unknown}
+
+    def coalesceDataSymbols(self, vramStart: int, vramEnd: int, stopAtAlignment: int|None=None, stopAtReferenced: bool=True, overlayCategory: str|None=None, segmentVromStart: int|None=None) -> int:
+        """Merges every run of contiguous unnamed autogenerated data symbols of the same type which start in the [`vramStart`, `vramEnd`) range into the first symbol of the run, which gets sized to cover the whole run.
+
+        A symbol starting at an address multiple of `stopAtAlignment` always starts a new run. If `stopAtReferenced` is `True` then referenced symbols also start a new run,
+        otherwise they are merged too. Overlay segments are used if `overlayCategory` and `segmentVromStart` are passed. Returns the amount of removed symbols"""
+        segment = self.globalSegment
+        if overlayCategory is not None and segmentVromStart is not None:
+            segment = self.overlaySegments[overlayCategory][segmentVromStart]
+
+        removed: list[int] = list()
+        runStart: ContextSymbol|None = None
+        runEnd = 0
+        for contextSym in list(segment.getSymbolsInRange(vramStart, vramEnd)):
+            symSize = contextSym.autodetectedSize if contextSym.autodetectedSize is not None else contextSym.getSize()
+
+            if not self._isCoalescableDataSymbol(contextSym):
+                runStart = None
+                continue
+
+            startsNewRun = runStart is None or contextSym.address != runEnd or contextSym.getType() != runStart.getType()
+            if stopAtAlignment is not None and contextSym.address % stopAtAlignment == 0:
+                startsNewRun = True
+            if contextSym.referenceCounter > 0 and stopAtReferenced:
+                startsNewRun = True
+
+            if runStart is None or startsNewRun:
+                runStart = contextSym
+            else:
+                removed.append(contextSym.address)
+                runStart.size = contextSym.address + symSize - runStart.address
+            runEnd = contextSym.address + symSize
+
+        for address in removed:
+            segment.symbols.remove(address)
+        return len(removed)
+
+    def renameSymbol(self, vram: int, newName: str, overlayCategory: str|None=None, segmentVromStart: int|None=None) -> ContextSymbol:
+        """Renames the symbol at `vram`. References are resolved by name when disassembling, so every reference emitted afterwards uses the new name.
+
+        Overlay segments are used if `overlayCategory` and `segmentVromStart` are passed. Raises a `RuntimeError` if there's no symbol at `vram`
+        or if another symbol of any segment already uses `newName`"""
+        segment = self.globalSegment
+        if overlayCategory is not None and segmentVromStart is not None:
+            segment = self.overlaySegments[overlayCategory][segmentVromStart]
+
+        contextSym = segment.getSymbol(vram, tryPlusOffset=False)
+        if contextSym is None:
+            raise RuntimeError(f"Can't rename the symbol at 0x{vram:08X} to '{newName}', there's no symbol at that address")
+
+        segments: list[SymbolsSegment] = [self.globalSegment]
+        for segmentsPerVrom in self.overlaySegments.values():
+            segments.extend(segmentsPerVrom.values())
+        for otherSegment in segments:
+            for otherSym in otherSegment.symbols.values():
+                if otherSym is not contextSym and otherSym.getName() == newName:
+                    raise RuntimeError(f"Can't rename the symbol at 0x{vram:08X} to '{newName}', the symbol at 0x{otherSym.vram:08X} already uses that name")
+
+        contextSym.name = newName
+        return contextSym
+
+    def snapshot(self) -> ContextSnapshot:
+        "Returns a snapshot which allows adding speculative symbols on top of this context without modifying it, until those are committed"
+        return ContextSnapshot(self)
+
+    @staticmethod
+    def _inferSymbolType(contextSym: ContextSymbol) -> InferredType|None:
+        accessTypes = sorted(contextSym.accessTypes)
+        evidence: list[str] = list()
+        confidence = InferredType.BASE_CONFIDENCE
+
+        if not contextSym.hasNoType() and not isinstance(contextSym.type, SymbolSpecialType):
+            inferredType = contextSym.getType()
+        elif contextSym.isMaybeString:
+            inferredType = "char"
+            evidence.append("string guesser")
+            confidence -= InferredType.GUESSER_PENALTY
+        elif contextSym.isMaybeDouble:
+            inferredType = "f64"
+            evidence.append("double guesser")
+            confidence -= InferredType.GUESSER_PENALTY
+        elif len(accessTypes) > 0:
+            inferredType = accessTypes[0]
+        else:
+            return None
+
+        if len(accessTypes) == 1 and accessTypes[0] == inferredType:
+            evidence.append(f"accessed as {inferredType}")
+            confidence += InferredType.SINGLE_ACCESS_TYPE_BONUS
+        elif len(accessTypes) > 0:
+            evidence.append(f"accessed as {'/'.join(accessTypes)}")
+            confidence -= InferredType.MIXED_ACCESS_TYPES_PENALTY
+
+        typeSize = ContextSymbol.getSizeOfType(inferredType)
+        if typeSize is not None:
+            if contextSym.vram % typeSize == 0:
+                evidence.append(f"aligned to {typeSize}")
+                confidence += InferredType.ALIGNED_BONUS
+            else:
+                evidence.append(f"not aligned to {typeSize}")
+                confidence -= InferredType.UNALIGNED_PENALTY
+
+        if contextSym.isIntConvertedToFloat:
+            evidence.append("converted to float")
+        if contextSym.referenceCounter > 0:
+            evidence.append(f"referenced {contextSym.referenceCounter} times")
+            confidence += InferredType.REFERENCED_BONUS
+        else:
+            evidence.append("unreferenced")
+            confidence -= InferredType.UNREFERENCED_PENALTY
+
+        return InferredType(contextSym.vram, contextSym.getName(), inferredType, evidence, min(max(confidence, 0.0), 1.0))
+
+    def getTypeInferenceReport(self) -> list[InferredType]:
+        """Lists the type guessed for every autogenerated symbol which isn't a function or a label, sorted by ascending confidence.
+
+        The evidence comes from the instructions which access each symbol, its alignment, how many times it is referenced and the string and double guessers"""
+        segments: list[SymbolsSegment] = [self.globalSegment]
+        for segmentsPerVrom in self.overlaySegments.values():
+            segments.extend(segmentsPerVrom.values())
+
+        report: list[InferredType] = list()
+        for segment in segments:
+            for contextSym in segment.symbols.values():
+                if contextSym.isUserDeclared or not contextSym.isAutogenerated:
+                    continue
+                if isinstance(contextSym.type, SymbolSpecialType):
+                    continue
+                inferred = self._inferSymbolType(contextSym)
+                if inferred is not None:
+                    report.append(inferred)
+
+        report.sort(key=lambda x: (x.confidence, x.vram))
+        return report
+
+    def saveTypeInferenceReport(self, f: TextIO) -> None:
+        "Writes `getTypeInferenceReport` as a csv. The evidence of each symbol is separated by `;`"
+        f.write("vram,name,type,confidence,evidence\n")
+        for inferred in self.getTypeInferenceReport():
+            f.write(f"0x{inferred.vram:08X},{inferred.name},{inferred.type},{inferred.confidence:.2f},{';'.join(inferred.evidence)}\n")
+
+    def getOrphanSymbols(self, includeUserDeclared: bool=False) -> list[ContextSymbol]:
+        """Lists the symbols of every segment which aren't referenced by any instruction or data word, sorted by vram.
+
+        Useful to find data which may be unused or wrongly sized. User declared symbols are skipped unless `includeUserDeclared` is passed"""
+        segments: list[SymbolsSegment] = [self.globalSegment]
+        for segmentsPerVrom in self.overlaySegments.values():
+            segments.extend(segmentsPerVrom.values())
+
+        orphans: list[ContextSymbol] = list()
+        for segment in segments:
+            for contextSym in segment.symbols.values():
+                if contextSym.isUserDeclared and not includeUserDeclared:
+                    continue
+                if contextSym.referenceCounter > 0 or len(contextSym.referenceFunctions) > 0:
+                    continue
+                orphans.append(contextSym)
+
+        orphans.sort(key=lambda x: x.vram)
+        return orphans
+
+    def saveStringEncodingsReport(self, f: TextIO) -> None:
+        "Writes a csv listing the encoding used to decode every string symbol, and if that encoding comes from the symbol itself (`override`) or from `GlobalConfig` (`global`)"
+        segments: list[SymbolsSegment] = [self.globalSegment]
+        for segmentsPerVrom in self.overlaySegments.values():
+            segments.extend(segmentsPerVrom.values())
+
+        f.write("vram,name,encoding,source\n")
+        for segment in segments:
+            for contextSym in segment.symbols.values():
+                if not contextSym.isString():
+                    continue
+                f.write(f"0x{contextSym.vram:08X},{contextSym.getName()},{contextSym.getStringEncoding()},{contextSym.getStringEncodingSource()}\n")
+
+
     @staticmethod
     def addParametersToArgParse(parser: argparse.ArgumentParser):
         contextParser = parser.add_argument_group("Context configuration")
 
         contextParser.add_argument("--save-context", help="Saves the context to a file", metavar="FILENAME")
+        contextParser.add_argument("--save-symbols-json", help="Saves the symbols of the context to a json file", metavar="FILENAME")
+        contextParser.add_argument("--save-splat-symbols", help="Saves the symbols of the context to a file using the symbol_addrs.txt format of splat", metavar="FILENAME")
+        contextParser.add_argument("--save-type-inference", help="Saves a csv listing the type guessed for each autogenerated symbol, with its evidence and confidence", metavar="FILENAME")
+        contextParser.add_argument("--save-string-encodings", help="Saves a csv listing the encoding used to decode each string symbol", metavar="FILENAME")
+        contextParser.add_argument("--sanitize-symbol-names", help="Replace the characters the assembler doesn't accept on symbol names with reversible escape sequences. Defaults to False", action=Utils.BooleanOptionalAction)
 
 
         csvConfig = parser.add_argument_group("Context .csv input files")
@@ -138,6 +639,10 @@ class Context:
         csvConfig.add_argument("--functions", help="Path to a functions csv", action="append")
         csvConfig.add_argument("--variables", help="Path to a variables csv", action="append")
         csvConfig.add_argument("--constants", help="Path to a constants csv", action="append")
+        csvConfig.add_argument("--symbols-json", help="Path to a symbols json, as produced by --save-symbols-json", action="append")
+        csvConfig.add_argument("--ignored-ranges", help="Path to a csv of `start,end` vram ranges which must not be symbolized, because they are handled elsewhere", action="append")
+        csvConfig.add_argument("--string-argument-functions", help="Path to a csv of `vram,argumentIndex` pairs of functions which take a string on that argument, like printf-like functions. Symbols passed on those arguments are typed as strings", action="append")
+        csvConfig.add_argument("--symbols-json-overwrite", help="Allow the symbols json to overwrite the names of already existing symbols", action=Utils.BooleanOptionalAction)
 
 
         symbolsConfig = parser.add_argument_group("Context default symbols configuration")
@@ -149,6 +654,9 @@ class Context:
 
 
     def parseArgs(self, args: argparse.Namespace):
+        if args.sanitize_symbol_names:
+            self.symbolNameSanitizer = SymbolNameSanitizer()
+
         if args.default_banned != False:
             self.fillDefaultBannedSymbols()
         if args.libultra_syms != False:
@@ -165,3 +673,101 @@ class Context:
         if args.constants is not None:
             for constantsPath in args.constants:
                 self.globalSegment.readConstantsCsv(constantsPath)
+        if args.ignored_ranges is not None:
+            for ignoredRangesPath in args.ignored_ranges:
+                self.readIgnoredRangesCsv(Path(ignoredRangesPath))
+        if args.string_argument_functions is not None:
+            for stringArgumentFunctionsPath in args.string_argument_functions:
+                self.readStringArgumentFunctionsCsv(Path(stringArgumentFunctionsPath))
+        if args.symbols_json is not None:
+            for symbolsJsonPath in args.symbols_json:
+                with open(symbolsJsonPath) as f:
+                    self.importSymbolsJson(f, overwrite=bool(args.symbols_json_overwrite))
+
+
+class ContextSnapshot:
+    """Layer of speculative symbols placed on top of a `Context`.
+
+    Symbols added or modified through the snapshot are only visible through the snapshot's queries. Existing symbols are copied the first
+    time those are requested for modification, so the context itself is never modified until `commit` is called"""
+
+    def __init__(self, context: Context):
+        self.context = context
+
+        self.speculativeSymbols: dict[SymbolsSegment, SortedDict[ContextSymbol]] = dict()
+        "Symbols added or modified through this snapshot, per segment of the context"
+
+    def getSegment(self, overlayCategory: str|None=None, segmentVromStart: int|None=None) -> SymbolsSegment:
+        if overlayCategory is not None and segmentVromStart is not None:
+            return self.context.overlaySegments[overlayCategory][segmentVromStart]
+        return self.context.globalSegment
+
+
+    def addSymbol(self, address: int, overlayCategory: str|None=None, segmentVromStart: int|None=None) -> ContextSymbol:
+        """Returns a speculative symbol at `address` which can be freely modified.
+
+        If the context already has a symbol at that address then the returned symbol starts as a copy of it"""
+        segment = self.getSegment(overlayCategory, segmentVromStart)
+        if segment not in self.speculativeSymbols:
+            self.speculativeSymbols[segment] = SortedDict()
+        speculative = self.speculativeSymbols[segment]
+
+        contextSym = speculative.get(address, None)
+        if contextSym is not None:
+            return contextSym
+
+        baseSym = segment.symbols.get(address, None)
+        if baseSym is not None:
+            contextSym = baseSym.copy()
+        else:
+            contextSym = ContextSymbol(address)
+            contextSym.parentSegment = segment
+            contextSym.overlayCategory = segment.overlayCategory
+        contextSym.isUserDeclared = True
+        speculative[address] = contextSym
+        return contextSym
+
+    def getSymbol(self, address: int, overlayCategory: str|None=None, segmentVromStart: int|None=None, tryPlusOffset: bool=True, checkUpperLimit: bool=True) -> ContextSymbol|None:
+        "Same as `SymbolsSegment.getSymbol`, but speculative symbols take precedence over the ones of the context"
+        segment = self.getSegment(overlayCategory, segmentVromStart)
+        baseSym = segment.getSymbol(address, tryPlusOffset=tryPlusOffset, checkUpperLimit=checkUpperLimit)
+        speculative = self.speculativeSymbols.get(segment)
+        if speculative is None:
+            return baseSym
+
+        if not GlobalConfig.PRODUCE_SYMBOLS_PLUS_OFFSET or not tryPlusOffset:
+            return speculative.get(address, baseSym)
+
+        if baseSym is not None and baseSym.vram in speculative:
+            # Use the modified copy instead
+            baseSym = speculative[baseSym.vram]
+            if checkUpperLimit and address >= baseSym.vram + baseSym.getSize():
+                baseSym = None
+
+        pair = speculative.getKeyRight(address, inclusive=True)
+        if pair is None:
+            return baseSym
+        symVram, contextSym = pair
+        if checkUpperLimit and address >= symVram + contextSym.getSize():
+            return baseSym
+        if baseSym is not None and baseSym.vram > symVram:
+            # The symbol of the context is closer to the address
+            return baseSym
+        return contextSym
+
+
+    def commit(self) -> None:
+        "Applies every speculative symbol to the context and clears this snapshot. Symbols which already existed on the context are updated in place"
+        for segment, speculative in self.speculativeSymbols.items():
+            for address, contextSym in speculative.items():
+                baseSym = segment.symbols.get(address, None)
+                if baseSym is None:
+                    segment.symbols[address] = contextSym
+                    continue
+                for field in dataclasses.fields(contextSym):
+                    setattr(baseSym, field.name, getattr(contextSym, field.name))
+        self.discard()
+
+    def discard(self) -> None:
+        "Drops every speculative symbol, leaving the context untouched"
+        self.speculativeSymbols.clear()
@@ -5,12 +5,17 @@
 
 from __future__ import annotations
 
+import copy
 import dataclasses
 import enum
-from typing import Callable
+from typing import Callable, TYPE_CHECKING
 
-from .GlobalConfig import GlobalConfig
+from .GlobalConfig import GlobalConfig, Compiler
 from .FileSectionType import FileSectionType
+from .ParentSegmentInfo import ParentSegmentInfo
+
+if TYPE_CHECKING:
+    from .SymbolsSegment import SymbolsSegment
 
 
 class SymbolSpecialType(enum.Enum):
@@ -20,6 +25,8 @@ class SymbolSpecialType(enum.Enum):
     jumptablelabel  = enum.auto()
     hardwarereg     = enum.auto()
     constant        = enum.auto()
+    altentry        = enum.auto()
+    "A global entry point in the middle of a function, which doesn't start a new function"
 
 
     def toStr(self) -> str:
@@ -39,16 +46,54 @@ class SymbolSpecialType(enum.Enum):
             return SymbolSpecialType.hardwarereg
         if symTypeStr == "@constant":
             return SymbolSpecialType.constant
+        if symTypeStr == "@altentry":
+            return SymbolSpecialType.altentry
         return None
 
 
+@enum.unique
+class SymbolOrigin(enum.Enum):
+    "What caused a symbol to be created"
+    userDeclared    = enum.auto()
+    hiLoPair        = enum.auto()
+    "Referenced by a `%hi`/`%lo` pair or a `%gp_rel` access"
+    jump            = enum.auto()
+    "Target of a `jal`, `j` or `jalr` instruction, or a pointer in a function pointer array"
+    branch          = enum.auto()
+    jumpTable       = enum.auto()
+    "A jump table or one of its entries"
+    dataPointer     = enum.auto()
+    "A word of a data section pointing to it"
+    analysis        = enum.auto()
+    "Created by the analysis itself, like the start of a section or a function found by splitting the code"
+
+
+def getStructFieldTypeSize(fieldType: str) -> int:
+    "Size of each element of a struct field. Anything that isn't a byte or a short is emitted as words"
+    typeSize = ContextSymbol.getSizeOfType(fieldType)
+    if typeSize is not None and typeSize < 4:
+        return typeSize
+    return 4
+
+
 @dataclasses.dataclass
 class ContextSymbol:
     address: int
     name: str|None = None
     size: int|None = None
+    autodetectedSize: int|None = None
+    "Size of this symbol as determined by the analysis of the section containing it"
     type: SymbolSpecialType|str|None = None
 
+    arrayCount: int|None = None
+    "Amount of elements of this symbol if it is an array. The type of each element is `type`"
+
+    structLayout: list[tuple[int, str]]|None = None
+    "Sorted list of `(offset, type)` fields for struct-like symbols with mixed width data. Each field's type lasts until the next field starts"
+
+    alignment: int|None = None
+    "Alignment of this symbol in bytes, emitted as a `.balign` directive. If `None` then `GlobalConfig.ASM_DATA_SYMBOLS_ALIGNMENT` is used"
+
     vromAddress: int|None = None
 
     sectionType: FileSectionType = FileSectionType.Unknown
@@ -59,11 +104,35 @@ class ContextSymbol:
     "Declared externally by the user, but it may have not been found yet"
     isAutogenerated: bool = False
     "This symbol was automatically generated by the disassembler"
+    origin: SymbolOrigin|None = None
+    "The first reason this symbol was created for. Only set for autogenerated symbols"
+    originVrom: int|None = None
+    "Vrom of the instruction or word which caused this symbol to be created, if known"
 
     isMaybeString: bool = False
+    isUnterminatedString: bool = False
+    "This string has no NUL terminator and lasts until the next symbol, so it is emitted with `.ascii` instead of `.asciz`"
+    encoding: str|None = None
+    "The encoding used to decode this symbol if it is a string. If `None` then `GlobalConfig.STRING_ENCODING` is used"
+    isMaybeDouble: bool = False
+    jumpTableTargetsRange: tuple[int, int]|None = None
+    "If this symbol is a jump table, the [`start`, `end`) vram range of the function using it. Entries pointing outside of it end the table"
+    isGpRelJumpTable: bool = False
+    "The entries of this jump table are offsets relative to `GlobalConfig.GP_VALUE` instead of addresses, so they are emitted with `.gpword`"
+    migrateToFunction: int|None = None
+    "Vram of the function this rodata symbol must be migrated to, regardless of which functions reference it"
+    isIntConvertedToFloat: bool = False
+    "The value of this symbol is loaded as an integer and then converted to a float by the code (`lw`, `mtc1`, `cvt.s.w`)"
+
+    accessTypes: set[str] = dataclasses.field(default_factory=set)
+    "Every type inferred from the instructions which access this symbol"
 
     referenceCounter: int = 0
     "How much this symbol is referenced by something else"
+    referenceFunctions: list[ContextSymbol] = dataclasses.field(default_factory=list, compare=False, repr=False)
+    "Functions which reference this symbol, sorted by the order they were found"
+    jumpTableCases: list[tuple[ContextSymbol, int]] = dataclasses.field(default_factory=list, compare=False, repr=False)
+    "If this symbol is a jump table label, every `(jumpTable, caseIndex)` pair which jumps to it"
 
     overlayCategory: str|None = None
 
@@ -77,6 +146,9 @@ class ContextSymbol:
 
     unknownSegment: bool = False
 
+    parentSegment: SymbolsSegment|None = dataclasses.field(default=None, compare=False, repr=False)
+    "The segment of the context this symbol belongs to, if any"
+
 
     @property
     def vram(self) -> int:
@@ -86,11 +158,44 @@ class ContextSymbol:
         return self.type is None or self.type == ""
 
 
+    def setOriginIfUnset(self, origin: SymbolOrigin, vrom: int|None=None) -> None:
+        if self.origin is None:
+            self.origin = origin
+            self.originVrom = vrom
+
+    def addReferenceFunction(self, funcSym: ContextSymbol) -> None:
+        for other in self.referenceFunctions:
+            if other is funcSym:
+                return
+        self.referenceFunctions.append(funcSym)
+
+    def addJumpTableCase(self, jumpTableSym: ContextSymbol, caseIndex: int) -> None:
+        for other, otherIndex in self.jumpTableCases:
+            if other is jumpTableSym and otherIndex == caseIndex:
+                return
+        self.jumpTableCases.append((jumpTableSym, caseIndex))
+
+    def copy(self) -> ContextSymbol:
+        """Returns a copy of this symbol which can be modified without affecting the original one.
+
+        Every mutable field gets its own copy. The symbols listed in `referenceFunctions` and `jumpTableCases` are not copied themselves"""
+        newSym = copy.copy(self)
+        if self.structLayout is not None:
+            newSym.structLayout = list(self.structLayout)
+        newSym.accessTypes = set(self.accessTypes)
+        newSym.referenceFunctions = list(self.referenceFunctions)
+        newSym.jumpTableCases = list(self.jumpTableCases)
+        return newSym
+
     def isTrustableFunction(self, rsp: bool=False) -> bool:
         """Checks if the function symbol should be trusted based on the current disassembler settings"""
         if self.unknownSegment:
             return False
 
+        # Alternative entry points belong to the function containing them
+        if self.type == SymbolSpecialType.altentry:
+            return False
+
         if GlobalConfig.TRUST_USER_FUNCTIONS and self.isUserDeclared:
             return True
 
@@ -122,11 +227,20 @@ class ContextSymbol:
                 return True
         return False
 
+    def isInteger(self) -> bool:
+        "Symbols typed as integers are never pointers"
+        return self.type in ("s32", "u32", "s64", "u64")
+
     def isFloat(self) -> bool:
         return self.type in ("f32", "Vec3f")
 
     def isDouble(self) -> bool:
-        return self.type == "f64"
+        if self.type == "f64":
+            return True
+        elif self.hasNoType(): # no type information, let's try to guess
+            if GlobalConfig.DOUBLE_GUESSER and self.isMaybeDouble:
+                return True
+        return False
 
     def isJumpTable(self) -> bool:
         return self.type == SymbolSpecialType.jumptable
@@ -149,11 +263,22 @@ class ContextSymbol:
         return self.getName().startswith(".")
 
     def isLateRodata(self) -> bool:
+        # late_rodata is an IDO thing, other compilers emit the jump tables and float constants of a function in order, alongside the rest of its rodata
+        if GlobalConfig.COMPILER != Compiler.IDO:
+            return False
         # if self.referenceCounter > 1: return False # ?
         return self.isJumpTable() or self.isFloat() or self.isDouble()
 
 
     def getDefaultName(self) -> str:
+        if self.parentSegment is not None and self.parentSegment.context is not None:
+            namesCallback = self.parentSegment.context.autogeneratedNamesCallback
+            if namesCallback is not None:
+                segmentInfo = ParentSegmentInfo(self.parentSegment.vromStart or 0, self.parentSegment.overlayCategory)
+                customName = namesCallback(self.address, self.type, self.vromAddress, segmentInfo)
+                if customName is not None:
+                    return customName
+
         suffix = ""
         if self.overlayCategory is not None:
             suffix = "_"
@@ -161,7 +286,7 @@ class ContextSymbol:
                 suffix += f"{self.vromAddress:06X}"
 
         if self.type is not None:
-            if self.type == SymbolSpecialType.function:
+            if self.type == SymbolSpecialType.function or self.type == SymbolSpecialType.altentry:
                 return f"func_{self.address:08X}{suffix}"
             if self.type == SymbolSpecialType.branchlabel:
                 return f".L{self.address:08X}{suffix}"
@@ -178,13 +303,22 @@ class ContextSymbol:
 
         return f"D_{self.address:06X}{suffix}"
 
-    def getName(self) -> str:
+    def getUnsanitizedName(self) -> str:
+        "Returns the name of this symbol as it was declared, even if it isn't a legal identifier for the assembler"
         if self.nameGetCallback is not None:
             return self.nameGetCallback(self)
         if self.name is None:
             return self.getDefaultName()
         return self.name
 
+    def getName(self) -> str:
+        name = self.getUnsanitizedName()
+        if self.parentSegment is not None and self.parentSegment.context is not None:
+            sanitizer = self.parentSegment.context.symbolNameSanitizer
+            if sanitizer is not None:
+                return sanitizer.sanitize(name)
+        return name
+
     def setNameIfUnset(self, name: str) -> bool:
         if self.name is None:
             self.type = name
@@ -198,9 +332,74 @@ class ContextSymbol:
         if self.nameGetCallback is None:
             self.nameGetCallback = callback
 
+    @staticmethod
+    def getSizeOfType(symType: SymbolSpecialType|str|None) -> int|None:
+        "Returns the size of a single element of the given type, or None if it isn't known"
+        if symType in {"s8", "u8"}:
+            return 1
+        if symType in {"s16", "u16"}:
+            return 2
+        if symType in {"s32", "u32", "f32"}:
+            return 4
+        if symType in {"s64", "u64", "f64"}:
+            return 8
+        return None
+
+    def getTypeSize(self) -> int|None:
+        "Returns the size of a single element of this symbol's type, or None if it isn't known"
+        return self.getSizeOfType(self.type)
+
+    def getArrayElementSize(self) -> int|None:
+        "Returns the size of each element if this symbol is an array and that size is known"
+        if self.arrayCount is None or self.arrayCount <= 0:
+            return None
+        elementSize = self.getTypeSize()
+        if elementSize is None and self.size is not None and self.size % self.arrayCount == 0:
+            elementSize = self.size // self.arrayCount
+        return elementSize
+
+    def setArray(self, elementType: str, count: int) -> None:
+        self.type = elementType
+        self.arrayCount = count
+
+    def setStructLayout(self, layout: list[tuple[int, str]]) -> None:
+        "Sets the struct layout of this symbol. Raises a `RuntimeError` if the fields are out of order, overlapping or unaligned"
+        previousEnd = 0
+        for offset, fieldType in layout:
+            fieldSize = getStructFieldTypeSize(fieldType)
+            if offset < previousEnd:
+                raise RuntimeError(f"Field at offset 0x{offset:X} of symbol '{self.getName()}' is out of order or overlaps with the previous field")
+            if offset % fieldSize != 0:
+                raise RuntimeError(f"Field at offset 0x{offset:X} of symbol '{self.getName()}' is not aligned to its type '{fieldType}'")
+            previousEnd = offset + fieldSize
+        self.structLayout = list(layout)
+
+    def getStructElementSize(self, offset: int) -> int:
+        "Returns the size of the element at `offset` according to the struct layout of this symbol. Data outside of any field is considered to be words"
+        if self.structLayout is None:
+            return 4
+        fieldType = None
+        nextFieldOffset = None
+        for fieldOffset, currentType in self.structLayout:
+            if fieldOffset > offset:
+                nextFieldOffset = fieldOffset
+                break
+            fieldType = currentType
+        elementSize = 4
+        if fieldType is not None:
+            elementSize = getStructFieldTypeSize(fieldType)
+        # Padding between fields may not fit a whole element
+        while elementSize > 1 and (offset % elementSize != 0 or (nextFieldOffset is not None and offset + elementSize > nextFieldOffset)):
+            elementSize //= 2
+        return elementSize
+
     def getSize(self) -> int:
         if self.size is not None:
             return self.size
+        if self.arrayCount is not None:
+            elementSize = self.getTypeSize()
+            if elementSize is not None:
+                return elementSize * self.arrayCount
         if self.type is not None:
             if self.type in {"s8", "u8"}:
                 return 1
@@ -225,6 +424,17 @@ class ContextSymbol:
             return True
         return False
 
+    def getStringEncoding(self) -> str:
+        if self.encoding is not None:
+            return self.encoding
+        return GlobalConfig.STRING_ENCODING
+
+    def getStringEncodingSource(self) -> str:
+        "Where the encoding returned by `getStringEncoding` comes from, either `override` if the symbol specifies it or `global` if it comes from `GlobalConfig`"
+        if self.encoding is not None:
+            return "override"
+        return "global"
+
     def getType(self) -> str:
         if self.type is None:
             return ""
@@ -7,9 +7,10 @@ from __future__ import annotations
 
 from typing import Generator
 
-from .GlobalConfig import GlobalConfig
+from .GlobalConfig import GlobalConfig, InputEndian
 from .ContextSymbols import ContextSymbol
 from .SymbolsSegment import SymbolsSegment
+from .ParentSegmentInfo import ParentSegmentInfo
 from .Context import Context
 from .FileSectionType import FileSectionType
 
@@ -66,6 +67,11 @@ class ElementBase:
         "The end of this element's VRAM"
         return self.vram + self.sizew * 4
 
+    @property
+    def parentSegmentInfo(self) -> ParentSegmentInfo:
+        "Identifies the segment this element belongs to"
+        return ParentSegmentInfo(self.segmentVromStart, self.overlayCategory)
+
 
     def setVram(self, vram: int):
         self.vram = vram
@@ -129,6 +135,10 @@ class ElementBase:
                 self._ownSegmentReference = self.context.globalSegment
         return self.context.globalSegment
 
+    def getEndian(self) -> InputEndian:
+        "The endianness of the segment this element belongs to, or `GlobalConfig.ENDIAN` if the segment doesn't override it"
+        return self.getSegment().getEndian()
+
     def getSegmentForVram(self, vram: int) -> SymbolsSegment:
         if self.overlayCategory is not None:
             # If this element is part of an overlay segment
@@ -191,6 +201,8 @@ class ElementBase:
 
             # Check only for the segment associated to this vrom address in this category
             segmentsPerVrom = self.context.overlaySegments.get(self.overlayCategory, None)
+            ownVramRange = False
+            isolated = False
             if segmentsPerVrom is not None:
                 overlaySegment = segmentsPerVrom.get(self.segmentVromStart, None)
                 if overlaySegment is not None:
@@ -198,15 +210,20 @@ class ElementBase:
                     contextSym = overlaySegment.getSymbol(vramAddress, tryPlusOffset=tryPlusOffset, checkUpperLimit=checkUpperLimit)
                     if contextSym is not None:
                         return contextSym
+                    ownVramRange = overlaySegment.isVramInRange(vramAddress)
+                    isolated = overlaySegment.isolated
 
             # If the vram was not part of that segment, then check for every other overlay category
-            for overlayCategory, segmentsPerVrom in self.context.overlaySegments.items():
-                if self.overlayCategory != overlayCategory:
-                    for overlaySegment in segmentsPerVrom.values():
-                        # if overlaySegment.isVramInRange(vramAddress):
-                        contextSym = overlaySegment.getSymbol(vramAddress, tryPlusOffset=tryPlusOffset, checkUpperLimit=checkUpperLimit)
-                        if contextSym is not None:
-                            return contextSym
+            # Addresses inside our own segment's range must not pick symbols from other segments which happen to use the same addresses
+            if not ownVramRange and not isolated:
+                for overlayCategory in self.context.getOverlayCategoriesBySearchOrder():
+                    segmentsPerVrom = self.context.overlaySegments[overlayCategory]
+                    if self.overlayCategory != overlayCategory:
+                        for overlaySegment in segmentsPerVrom.values():
+                            # if overlaySegment.isVramInRange(vramAddress):
+                            contextSym = overlaySegment.getSymbol(vramAddress, tryPlusOffset=tryPlusOffset, checkUpperLimit=checkUpperLimit)
+                            if contextSym is not None:
+                                return contextSym
 
         # if self.context.globalSegment.isVramInRange(vramAddress):
         contextSym = self.context.globalSegment.getSymbol(vramAddress, tryPlusOffset=tryPlusOffset, checkUpperLimit=checkUpperLimit)
@@ -236,6 +253,12 @@ class ElementBase:
         return segment.getConstant(constantValue)
 
 
+    def getNextSymbolLocalOffset(self, localOffset: int) -> int:
+        "Returns the local offset of the first symbol which starts after `localOffset` inside this element, or the size of this element if there's none"
+        for symVram, _ in self.getSymbolsRange(self.getVramOffset(localOffset + 1), self.vramEnd):
+            return symVram - self.vram
+        return self.sizew * 4
+
     def addSymbol(self, vramAddress: int, sectionType: FileSectionType=FileSectionType.Unknown, isAutogenerated: bool=False, symbolVrom: int|None=None) -> ContextSymbol:
         if symbolVrom is not None:
             segment = self.getSegmentForVrom(symbolVrom)
@@ -278,6 +301,8 @@ class ElementBase:
 
 
     def addPointerInDataReference(self, pointer: int) -> None:
+        if self.context.isAddressIgnored(pointer):
+            return
         segment = self.getSegmentForVram(pointer)
         segment.addPointerInDataReference(pointer)
 
@@ -297,9 +322,30 @@ class ElementBase:
         return segment.getLoPatch(loInstrVram)
 
     def canUseAddendsOnData(self) -> bool:
+        if GlobalConfig.DATA_REFERENCES_WITH_ADDENDS:
+            return True
         segment = self.getSegmentForVram(self.vram)
         return self.vram in segment.dataSymbolsWithReferencesWithAddends
 
+    def getSymbolForDataReference(self, address: int, canReferenceSymbolsWithAddends: bool) -> ContextSymbol|None:
+        """Searches the symbol a data pointer should be emitted with.
+
+        If addends are allowed the pointer may land inside a symbol, as long as the addend isn't bigger than `GlobalConfig.DATA_ADDENDS_MAX`,
+        or right before a symbol if it isn't farther than `GlobalConfig.DATA_NEGATIVE_ADDENDS_MAX`"""
+        contextSym = self.getSymbol(address, tryPlusOffset=canReferenceSymbolsWithAddends)
+        if not canReferenceSymbolsWithAddends:
+            return contextSym
+
+        if contextSym is not None:
+            if address - contextSym.vram > GlobalConfig.DATA_ADDENDS_MAX:
+                return None
+            return contextSym
+
+        if GlobalConfig.DATA_NEGATIVE_ADDENDS_MAX > 0:
+            for _, nextSym in self.getSymbolsRange(address + 1, address + GlobalConfig.DATA_NEGATIVE_ADDENDS_MAX + 1):
+                return nextSym
+        return None
+
     def canUseConstantsOnData(self) -> bool:
         segment = self.getSegmentForVram(self.vram)
         return self.vram in segment.dataReferencingConstants
@@ -19,6 +19,9 @@ class FileSectionType(enum.Enum):
     Bss     = 4
     Reloc   = 5
 
+    Reginfo  = 6
+    AbiFlags = 7
+
     @staticmethod
     def fromId(sectionId: int) -> FileSectionType:
         if sectionId == 1:
@@ -45,6 +48,10 @@ class FileSectionType(enum.Enum):
             return FileSectionType.Bss
         if x == ".reloc":
             return FileSectionType.Reloc
+        if x == ".reginfo":
+            return FileSectionType.Reginfo
+        if x == ".MIPS.abiflags":
+            return FileSectionType.AbiFlags
         return FileSectionType.Invalid
 
     def toStr(self) -> str:
@@ -58,6 +65,10 @@ class FileSectionType(enum.Enum):
             return ".bss"
         if self == FileSectionType.Reloc:
             return ".reloc"
+        if self == FileSectionType.Reginfo:
+            return ".reginfo"
+        if self == FileSectionType.AbiFlags:
+            return ".MIPS.abiflags"
         return ""
 
     def toCapitalizedStr(self) -> str:
@@ -71,6 +82,10 @@ class FileSectionType(enum.Enum):
             return "Bss"
         if self == FileSectionType.Reloc:
             return "Reloc"
+        if self == FileSectionType.Reginfo:
+            return "Reginfo"
+        if self == FileSectionType.AbiFlags:
+            return "AbiFlags"
         return ""
 
     def toSectionName(self) -> str:
@@ -84,6 +99,10 @@ class FileSectionType(enum.Enum):
             return ".bss"
         if self == FileSectionType.Reloc:
             return ".ovl"
+        if self == FileSectionType.Reginfo:
+            return ".reginfo"
+        if self == FileSectionType.AbiFlags:
+            return ".MIPS.abiflags"
         return ""
 
 FileSections_ListBasic = [FileSectionType.Text, FileSectionType.Data, FileSectionType.Rodata, FileSectionType.Bss]
@@ -32,6 +32,21 @@ class Compiler(enum.Enum):
             return Compiler.UNKNOWN
         return Compiler(value)
 
+@enum.unique
+class Abi(enum.Enum):
+    O32 = "o32"
+    N32 = "n32"
+    N64 = "n64"
+
+    @staticmethod
+    def fromStr(value: str) -> Abi:
+        return Abi(value.lower())
+
+    @property
+    def hasGpr64Bits(self) -> bool:
+        "The general purpose registers are 64 bits wide, so 64-bit instructions like `daddiu` are used for pointer arithmetic, including the stack pointer"
+        return self != Abi.O32
+
 
 class GlobalConfig:
     DISASSEMBLE_UNKNOWN_INSTRUCTIONS: bool = False
@@ -42,8 +57,48 @@ class GlobalConfig:
     TRUST_USER_FUNCTIONS: bool = True
     TRUST_JAL_FUNCTIONS: bool = True
 
+    UNTERMINATED_FUNCTIONS_AS_DATA: bool = False
+    """Emit the last function of a section as data if the section ends before that function returns, instead of sizing the function up to the end of the section"""
+    UNALIGNED_TARGETS_AS_DATA: bool = False
+    """Emit functions which call addresses not aligned to 4 bytes through a register as data. Those functions are usually data which was decoded as code"""
+    VALIDATE_STACK_FRAMES: bool = False
+    """Warn about functions whose stack pointer adjustments don't balance (the prologue's `addiu $sp, $sp, -N` doesn't match the epilogue's `addiu $sp, $sp, N`), which usually means a wrong function boundary"""
+    MAX_AUTOGENERATED_SYMBOLS_PER_SECTION: int = 0
+    """Abort the analysis of a section with a `SectionAnalysisError` if it contains more autogenerated symbols than this, which usually means data is being analyzed as code. 0 disables the limit"""
+
     STRING_GUESSER: bool = True
     """Rodata string guesser"""
+    STRING_GUESSER_MIN_LENGTH: int = 1
+    """Minimum amount of bytes (not counting the NUL terminator) a guessed string must have"""
+    STRING_GUESSER_MIN_PRINTABLE_RATIO: float = 0.0
+    """Minimum ratio (between 0 and 1) of printable characters a guessed string must have"""
+    STRING_GUESSER_UNTERMINATED: bool = False
+    """Allow the string guesser to detect strings without a NUL terminator which run until the next symbol, emitting them with `.ascii`"""
+    STRING_ENCODING: str = "EUC-JP"
+    """Encoding used to decode strings, unless the symbol specifies its own"""
+    STRING_ASCII_STRICT: bool = False
+    """When strings are decoded as ASCII, only accept printable characters (`0x20` to `0x7E`) and the `\\t`, `\\n`, `\\r`, `\\a`, `\\f` and escape control characters. Any other byte makes the symbol not a string"""
+    STRING_SHARED_REFERENCES_THRESHOLD: int = 2
+    """Strings referenced by at least this amount of different functions are kept in the shared rodata instead of being migrated to any of those functions, while strings referenced by fewer functions can be migrated even if they are referenced many times.
+
+    `0` disables this check, making strings follow the same rule as any other rodata symbol: only migrate them if they are referenced exactly once"""
+
+    DOUBLE_GUESSER: bool = False
+    """Guess untyped 8-byte aligned rodata symbols which are 8 bytes long, are not accessed directly by any instruction and hold a normal double (not zero, denormal, NaN or infinity) are doubles
+
+    Symbols accessed by `ldc1`/`sdc1` are already typed as doubles by the analysis, so this only affects symbols referenced through pointers or not referenced at all"""
+
+    DATA_AGGRESSIVE_POINTER_DETECTION: bool = False
+    """Consider every word of a data section pointing to a known segment as a possible pointer, instead of only the ones pointing after the section. Words of symbols typed as integers (like `s32`) are never considered pointers"""
+    DATA_REFERENCES_WITH_ADDENDS: bool = False
+    """Allow every data symbol to reference other symbols with addends (like `array + 0x10`) when the pointer lands inside a known symbol, instead of creating a new symbol"""
+    DATA_ADDENDS_MAX: int = 0x1000
+    """Biggest addend allowed for data references. Pointers farther than this from the start of the symbol get their own symbol"""
+    DATA_NEGATIVE_ADDENDS_MAX: int = 0
+    """Biggest negative addend allowed for data references (like `array - 0x4`), for pointers placed right before a symbol. Disabled if 0"""
+    DATA_NULL_POINTERS: bool = True
+    """Treat the zero words next to the pointers of a data section as null pointers, keeping them in the same symbol and always emitting them as `.word 0`.
+    If disabled, a new symbol is started on every change between pointers and zero words, splitting the pointer array"""
 
     AUTOGENERATED_NAMES_BASED_ON_SECTION_TYPE: bool = True
     """Name autogenerated symbols after the section those are come from
@@ -57,6 +112,11 @@ class GlobalConfig:
 
     COMPILER: Compiler = Compiler.IDO
 
+    ABI: Abi = Abi.O32
+    """ABI the disassembled code was compiled for
+
+    Selects the register names used by rabbitizer and, for the ABIs with 64-bit registers, allows `daddiu $sp, $sp, N` to be recognized as a stack frame adjustment"""
+
     ENDIAN: InputEndian = InputEndian.BIG
     """Endian for input binary files"""
 
@@ -76,6 +136,26 @@ class GlobalConfig:
     ASM_COMMENT: bool = True
     """Toggle the comments in generated assembly code"""
     ASM_COMMENT_OFFSET_WIDTH: int = 6
+    ASM_COMMENT_FORMAT: str = "/* {rom} {vram} {bytes}*/"
+    """Template of the comment emitted before each instruction and data directive
+
+    `{rom}` is replaced with the file offset, `{vram}` with the address and `{bytes}` with the raw word followed by a space, or with nothing if the line doesn't have a raw word"""
+    ASM_COMMENT_OWN_LINE: bool = False
+    """Emit the comment of each instruction and data directive on its own line, before the line it refers to. Needed for line comment syntaxes (like `; {vram}`), which would comment out the rest of the line otherwise"""
+    ASM_COMMENT_SYMBOL_REFERENCES: bool = False
+    """Append a comment with the resolved address or symbol (like `# -> D_80123456`) to the instructions completing a reference to a symbol"""
+    ASM_COMMENT_FRAME_SIZE: bool = False
+    """Append a comment with the stack frame size (like `# frame 0x60`) to the instructions which reserve the stack of a function"""
+    ASM_COMMENT_FLOAT_CONSTANTS: bool = False
+    """Append a comment with the decoded value (like `# 1.5f`) to the `mtc1` instructions which move a float constant built with `lui`/`ori`/`addiu` into a float register. Pairs of `mtc1` filling both halves of a double are decoded as a double"""
+    ASM_COMMENT_CACHE_OPS: bool = False
+    """Append a comment with the name of the operation and the targeted cache (like `# index_store_tag (dcache)`) to the `cache` instructions. Use `--cop0-named-registers` to name the Coprocessor 0 registers"""
+    ASM_COMMENT_JUMP_TABLE_CASES: bool = False
+    """Comment the case index of each jump table entry, and list the jump tables and cases which reach each jump table label of the code"""
+    ASM_COMMENT_FUNCTION_REFERENCES: bool = False
+    """Emit a comment footer after each function listing the functions which call it and the symbols it references"""
+    ASM_COMMENT_FUNCTION_REFERENCES_MAX: int = 10
+    """Maximum amount of entries listed on each list of the references footer. The remaining ones are summarized as `... (N more)`"""
     GLABEL_ASM_COUNT: bool = True
     """Toggle the glabel count comment on functions"""
 
@@ -84,6 +164,28 @@ class GlobalConfig:
     ASM_TEXT_ENT_LABEL: str = ""
     ASM_TEXT_END_LABEL: str = ""
     ASM_TEXT_FUNC_AS_LABEL: bool = False
+    ASM_PSEUDO_LA: bool = False
+    """Emit a `la` pseudo-instruction for each `lui`/`addiu` pair which loads the address of a symbol into a register, if both instructions are next to each other and use the same register"""
+    ASM_TEXT_SET_NOAT: bool = False
+    """Wrap each function which uses `$at` explicitly with `.set noat` and `.set at` directives, so it assembles even when it isn't preceded by the section's `.set noat` (i.e. when each function is included on its own)"""
+    ASM_TEXT_PADDING_AS_BALIGN: bool = False
+    """Emit the `nop`s placed after the `jr $ra` of a function to pad it up to an alignment boundary as a `.balign` directive instead of instructions"""
+    ASM_TEXT_DELAY_SLOT_INDENT: str = " "
+    """Indentation added before the instructions placed on a delay slot. The operands are shifted back by the same amount, so they stay aligned with the other instructions. Should only contain whitespace"""
+    ASM_GP_64: bool = True
+    """Emit `.set gp=64` in the prelude of the generated files, so the assembler accepts 64-bit instructions (`ld`, `sd`, `daddu`, etc). Disable it for code built for 32-bit registers"""
+    ASM_SIZE_DIRECTIVE: str = ""
+    """Template emitted after the end of every function and data symbol, for example `.size {name}, . - {name}`. `{name}` is replaced with the name of the symbol. Nothing is emitted if empty"""
+    ASM_DATA_SYMBOLS_ALIGNMENT: int = 0
+    """Alignment (in bytes) emitted as a `.balign` directive before every data symbol which doesn't declare its own alignment. Doubles are aligned to at least 8 bytes. Disabled if 0"""
+    ASM_DATA_FILL_THRESHOLD: int = 0
+    """Collapse runs of identical words of data symbols which are at least this amount of bytes long into a single `.fill` directive. Runs never span over labels or words referencing symbols. Disabled if 0"""
+    ASM_BSS_AS_COMMON_SYMBOLS: bool = False
+    """Emit every bss symbol as a common symbol (`.comm name, size`) instead of a label followed by a `.space` directive, leaving its placement to the linker"""
+    ASM_STRING_POOLS: bool = False
+    """Emit every NUL-separated string of a string symbol with its own `.asciz` directive, instead of only the first one"""
+    ASM_SLL_HINTS_AS_MNEMONICS: bool = False
+    """Render the `sll $zero, $zero, N` encoded hints with their canonical mnemonics (`ssnop`, `ehb` and `pause`)"""
 
     PRINT_NEW_FILE_BOUNDARIES: bool = False
     """Print to stdout every file boundary found in .text and .rodata"""
@@ -120,7 +222,25 @@ class GlobalConfig:
 
         backendConfig.add_argument("--disasm-unknown", help=f"Force disassembling functions with unknown instructions. Defaults to {GlobalConfig.DISASSEMBLE_UNKNOWN_INSTRUCTIONS}", action=Utils.BooleanOptionalAction)
 
+        backendConfig.add_argument("--unterminated-functions-as-data", help=f"Emit the last function of a section as data if the section ends before that function returns. Defaults to {GlobalConfig.UNTERMINATED_FUNCTIONS_AS_DATA}", action=Utils.BooleanOptionalAction)
+        backendConfig.add_argument("--unaligned-targets-as-data", help=f"Emit functions which call addresses not aligned to 4 bytes through a register as data. Defaults to {GlobalConfig.UNALIGNED_TARGETS_AS_DATA}", action=Utils.BooleanOptionalAction)
+        backendConfig.add_argument("--max-autogenerated-symbols-per-section", help=f"Abort the analysis of a section if it contains more autogenerated symbols than this. Expects a number, 0 disables it. Defaults to {GlobalConfig.MAX_AUTOGENERATED_SYMBOLS_PER_SECTION}")
+        backendConfig.add_argument("--validate-stack-frames", help=f"Warn about functions whose stack pointer adjustments don't balance, which usually means a wrong function boundary. Defaults to {GlobalConfig.VALIDATE_STACK_FRAMES}", action=Utils.BooleanOptionalAction)
+
+        backendConfig.add_argument("--data-aggressive-pointer-detection", help=f"Consider every word of a data section pointing to a known segment as a possible pointer. Defaults to {GlobalConfig.DATA_AGGRESSIVE_POINTER_DETECTION}", action=Utils.BooleanOptionalAction)
+        backendConfig.add_argument("--data-addends", help=f"Allow every data symbol to reference other symbols with addends when the pointer lands inside a known symbol. Defaults to {GlobalConfig.DATA_REFERENCES_WITH_ADDENDS}", action=Utils.BooleanOptionalAction)
+        backendConfig.add_argument("--data-addends-max", help=f"Biggest addend allowed for data references. Expects a number. Defaults to 0x{GlobalConfig.DATA_ADDENDS_MAX:X}")
+        backendConfig.add_argument("--data-negative-addends-max", help=f"Biggest negative addend allowed for data references, for pointers placed right before a symbol. Expects a number. Defaults to 0x{GlobalConfig.DATA_NEGATIVE_ADDENDS_MAX:X}")
+        backendConfig.add_argument("--data-null-pointers", help=f"Treat the zero words next to the pointers of a data section as null pointers instead of splitting the pointer array. Defaults to {GlobalConfig.DATA_NULL_POINTERS}", action=Utils.BooleanOptionalAction)
+
         backendConfig.add_argument("--string-guesser", help=f"Toggles the string guesser feature. Defaults to {GlobalConfig.STRING_GUESSER}", action=Utils.BooleanOptionalAction)
+        backendConfig.add_argument("--string-guesser-min-length", help=f"Minimum amount of bytes a guessed string must have. Defaults to {GlobalConfig.STRING_GUESSER_MIN_LENGTH}")
+        backendConfig.add_argument("--string-guesser-min-printable-ratio", help=f"Minimum ratio (between 0 and 1) of printable characters a guessed string must have. Defaults to {GlobalConfig.STRING_GUESSER_MIN_PRINTABLE_RATIO}")
+        backendConfig.add_argument("--string-guesser-unterminated", help=f"Allow the string guesser to detect strings without a NUL terminator which run until the next symbol. Defaults to {GlobalConfig.STRING_GUESSER_UNTERMINATED}", action=Utils.BooleanOptionalAction)
+        backendConfig.add_argument("--string-encoding", help=f"Sets the encoding used to decode strings. Defaults to {GlobalConfig.STRING_ENCODING}")
+        backendConfig.add_argument("--string-ascii-strict", help=f"When strings are decoded as ASCII, reject any byte which isn't a printable character or a common control character. Defaults to {GlobalConfig.STRING_ASCII_STRICT}", action=Utils.BooleanOptionalAction)
+        backendConfig.add_argument("--string-shared-references-threshold", help=f"Strings referenced by at least this amount of different functions are not migrated to any of them, while strings referenced by fewer functions can be migrated. 0 disables this check, migrating strings only if they are referenced once. Defaults to {GlobalConfig.STRING_SHARED_REFERENCES_THRESHOLD}")
+        backendConfig.add_argument("--double-guesser", help=f"Toggles the double guesser feature, which guesses 8 bytes rodata symbols not accessed directly by any instruction are doubles. Defaults to {GlobalConfig.DOUBLE_GUESSER}", action=Utils.BooleanOptionalAction)
 
         backendConfig.add_argument("--name-vars-by-section", help=f"Toggles the naming-after-section feature for autogenerated names. This means autogenerated symbols get a R_ or B_ prefix if the symbol is from a rodata or bss section. Defaults to {GlobalConfig.AUTOGENERATED_NAMES_BASED_ON_SECTION_TYPE}", action=Utils.BooleanOptionalAction)
         backendConfig.add_argument("--name-vars-by-type", help=f"Toggles the naming-after-type feature for autogenerated names. This means autogenerated symbols can get a STR_, FLT_ or DBL_ prefix if the symbol is a string, float or double. Defaults to {GlobalConfig.AUTOGENERATED_NAMES_BASED_ON_DATA_TYPE}", action=Utils.BooleanOptionalAction)
@@ -140,6 +260,15 @@ class GlobalConfig:
         miscConfig = parser.add_argument_group("Disassembler misc options")
 
         miscConfig.add_argument("--asm-comments", help=f"Toggle the comments in generated assembly code. Defaults to {GlobalConfig.ASM_COMMENT}", action=Utils.BooleanOptionalAction)
+        miscConfig.add_argument("--asm-comment-symbol-refs", help=f"Append a comment with the resolved symbol to the instructions completing a reference to it. Defaults to {GlobalConfig.ASM_COMMENT_SYMBOL_REFERENCES}", action=Utils.BooleanOptionalAction)
+        miscConfig.add_argument("--asm-comment-frame-size", help=f"Append a comment with the stack frame size to the instructions which reserve the stack of a function. Defaults to {GlobalConfig.ASM_COMMENT_FRAME_SIZE}", action=Utils.BooleanOptionalAction)
+        miscConfig.add_argument("--asm-comment-cache-ops", help=f"Append a comment with the name of the operation and the targeted cache to the `cache` instructions. Defaults to {GlobalConfig.ASM_COMMENT_CACHE_OPS}", action=Utils.BooleanOptionalAction)
+        miscConfig.add_argument("--asm-comment-float-constants", help=f"Append a comment with the decoded value to the `mtc1` instructions which move a float constant built on a register. Defaults to {GlobalConfig.ASM_COMMENT_FLOAT_CONSTANTS}", action=Utils.BooleanOptionalAction)
+        miscConfig.add_argument("--asm-comment-jtbl-cases", help=f"Comment the case index of each jump table entry, and the jump tables and cases which reach each jump table label. Defaults to {GlobalConfig.ASM_COMMENT_JUMP_TABLE_CASES}", action=Utils.BooleanOptionalAction)
+        miscConfig.add_argument("--asm-comment-func-refs", help=f"Emit a comment footer after each function listing its callers and the symbols it references. Defaults to {GlobalConfig.ASM_COMMENT_FUNCTION_REFERENCES}", action=Utils.BooleanOptionalAction)
+        miscConfig.add_argument("--asm-comment-func-refs-max", help=f"Maximum amount of entries listed on each list of the references footer. Defaults to {GlobalConfig.ASM_COMMENT_FUNCTION_REFERENCES_MAX}")
+        miscConfig.add_argument("--asm-comment-format", help=f"Template of the comment emitted before each instruction and data directive. `{{rom}}`, `{{vram}}` and `{{bytes}}` are replaced with the file offset, address and raw word of the line. Defaults to `{GlobalConfig.ASM_COMMENT_FORMAT}`")
+        miscConfig.add_argument("--asm-comment-own-line", help=f"Emit the comment of each instruction and data directive on its own line, as needed by line comment syntaxes. Defaults to {GlobalConfig.ASM_COMMENT_OWN_LINE}", action=Utils.BooleanOptionalAction)
         miscConfig.add_argument("--comment-offset-width", help=f"Sets the zeroes width padding for the file offset comment. Defaults to {GlobalConfig.ASM_COMMENT_OFFSET_WIDTH}", action=Utils.BooleanOptionalAction)
         miscConfig.add_argument("--glabel-count", help=f"Toggle glabel count comment. Defaults to {GlobalConfig.GLABEL_ASM_COUNT}", action=Utils.BooleanOptionalAction)
 
@@ -147,7 +276,18 @@ class GlobalConfig:
         miscConfig.add_argument("--asm-data-label", help=f"Changes the label used to declare data symbols. Defaults to {GlobalConfig.ASM_DATA_LABEL}")
         miscConfig.add_argument("--asm-ent-label", help=f"Tells the disassembler to start using an ent label for functions")
         miscConfig.add_argument("--asm-end-label", help=f"Tells the disassembler to start using an end label for functions")
+        miscConfig.add_argument("--asm-size-directive", help="Sets a template emitted after every function and data symbol, like `.size {name}, . - {name}`. `{name}` is replaced with the name of the symbol. Defaults to not emitting anything")
+        miscConfig.add_argument("--asm-data-fill-threshold", help=f"Collapse runs of identical words of data symbols which are at least this amount of bytes long into a single `.fill` directive. Expects a number, 0 disables it. Defaults to 0x{GlobalConfig.ASM_DATA_FILL_THRESHOLD:X}")
+        miscConfig.add_argument("--asm-bss-comm", help=f"Emit every bss symbol as a common symbol (`.comm name, size`) instead of a label followed by a `.space` directive. Defaults to {GlobalConfig.ASM_BSS_AS_COMMON_SYMBOLS}", action=Utils.BooleanOptionalAction)
+        miscConfig.add_argument("--asm-data-symbols-alignment", help=f"Alignment emitted as a `.balign` directive before every data symbol which doesn't declare its own alignment. Doubles are aligned to at least 8 bytes. Expects a number, 0 disables it. Defaults to {GlobalConfig.ASM_DATA_SYMBOLS_ALIGNMENT}")
         miscConfig.add_argument("--asm-func-as-label", help=f"Toggle adding the function name as an additional label. Defaults to {GlobalConfig.ASM_TEXT_FUNC_AS_LABEL}", action=Utils.BooleanOptionalAction)
+        miscConfig.add_argument("--asm-string-pools", help=f"Toggle emitting every NUL-separated string of a string symbol with its own .asciz directive. Defaults to {GlobalConfig.ASM_STRING_POOLS}", action=Utils.BooleanOptionalAction)
+        miscConfig.add_argument("--asm-sll-hints", help=f"Toggle rendering the `sll $zero, $zero, N` encoded hints as `ssnop`, `ehb` or `pause`. Defaults to {GlobalConfig.ASM_SLL_HINTS_AS_MNEMONICS}", action=Utils.BooleanOptionalAction)
+        miscConfig.add_argument("--asm-gp64", help=f"Toggle emitting `.set gp=64` in the prelude of the generated files. Defaults to {GlobalConfig.ASM_GP_64}", action=Utils.BooleanOptionalAction)
+        miscConfig.add_argument("--asm-pseudo-la", help=f"Emit a `la` pseudo-instruction for each adjacent `lui`/`addiu` pair which loads the address of a symbol. Defaults to {GlobalConfig.ASM_PSEUDO_LA}", action=Utils.BooleanOptionalAction)
+        miscConfig.add_argument("--asm-text-set-noat", help=f"Wrap each function which uses `$at` explicitly with `.set noat` and `.set at` directives. Defaults to {GlobalConfig.ASM_TEXT_SET_NOAT}", action=Utils.BooleanOptionalAction)
+        miscConfig.add_argument("--asm-text-padding-balign", help=f"Emit the `nop`s which pad a function after its `jr $ra` up to an alignment boundary as a `.balign` directive. Defaults to {GlobalConfig.ASM_TEXT_PADDING_AS_BALIGN}", action=Utils.BooleanOptionalAction)
+        miscConfig.add_argument("--asm-text-delay-slot-indent", help=f"Changes the indentation added before the instructions placed on a delay slot. Defaults to {GlobalConfig.ASM_TEXT_DELAY_SLOT_INDENT!r}")
 
         miscConfig.add_argument("--print-new-file-boundaries", help=f"Print to stdout any new file boundary found. Defaults to {GlobalConfig.PRINT_NEW_FILE_BOUNDARIES}", action=Utils.BooleanOptionalAction)
 
@@ -173,8 +313,42 @@ class GlobalConfig:
         if args.disasm_unknown is not None:
             GlobalConfig.DISASSEMBLE_UNKNOWN_INSTRUCTIONS = args.disasm_unknown
 
+        if args.unterminated_functions_as_data is not None:
+            GlobalConfig.UNTERMINATED_FUNCTIONS_AS_DATA = args.unterminated_functions_as_data
+        if args.unaligned_targets_as_data is not None:
+            GlobalConfig.UNALIGNED_TARGETS_AS_DATA = args.unaligned_targets_as_data
+        if args.validate_stack_frames is not None:
+            GlobalConfig.VALIDATE_STACK_FRAMES = args.validate_stack_frames
+        if args.max_autogenerated_symbols_per_section is not None:
+            GlobalConfig.MAX_AUTOGENERATED_SYMBOLS_PER_SECTION = int(args.max_autogenerated_symbols_per_section, 0)
+
+        if args.data_aggressive_pointer_detection is not None:
+            GlobalConfig.DATA_AGGRESSIVE_POINTER_DETECTION = args.data_aggressive_pointer_detection
+        if args.data_addends is not None:
+            GlobalConfig.DATA_REFERENCES_WITH_ADDENDS = args.data_addends
+        if args.data_addends_max is not None:
+            GlobalConfig.DATA_ADDENDS_MAX = int(args.data_addends_max, 0)
+        if args.data_negative_addends_max is not None:
+            GlobalConfig.DATA_NEGATIVE_ADDENDS_MAX = int(args.data_negative_addends_max, 0)
+        if args.data_null_pointers is not None:
+            GlobalConfig.DATA_NULL_POINTERS = args.data_null_pointers
+
         if args.string_guesser is not None:
             GlobalConfig.STRING_GUESSER = args.string_guesser
+        if args.string_guesser_min_length is not None:
+            GlobalConfig.STRING_GUESSER_MIN_LENGTH = int(args.string_guesser_min_length, 0)
+        if args.string_guesser_min_printable_ratio is not None:
+            GlobalConfig.STRING_GUESSER_MIN_PRINTABLE_RATIO = float(args.string_guesser_min_printable_ratio)
+        if args.string_guesser_unterminated is not None:
+            GlobalConfig.STRING_GUESSER_UNTERMINATED = args.string_guesser_unterminated
+        if args.string_encoding is not None:
+            GlobalConfig.STRING_ENCODING = args.string_encoding
+        if args.string_ascii_strict is not None:
+            GlobalConfig.STRING_ASCII_STRICT = args.string_ascii_strict
+        if args.string_shared_references_threshold is not None:
+            GlobalConfig.STRING_SHARED_REFERENCES_THRESHOLD = int(args.string_shared_references_threshold, 0)
+        if args.double_guesser is not None:
+            GlobalConfig.DOUBLE_GUESSER = args.double_guesser
 
         if args.name_vars_by_section is not None:
             GlobalConfig.AUTOGENERATED_NAMES_BASED_ON_SECTION_TYPE = args.name_vars_by_section
@@ -206,8 +380,30 @@ class GlobalConfig:
 
         if args.asm_comments is not None:
             GlobalConfig.ASM_COMMENT = args.asm_comments
+        if args.asm_comment_format is not None:
+            try:
+                args.asm_comment_format.format(rom="", vram="", bytes="")
+            except (KeyError, IndexError, ValueError) as e:
+                raise RuntimeError(f"Invalid comment format {args.asm_comment_format!r}, only the `{{rom}}`, `{{vram}}` and `{{bytes}}` fields are allowed: {e!r}")
+            GlobalConfig.ASM_COMMENT_FORMAT = args.asm_comment_format
+        if args.asm_comment_own_line is not None:
+            GlobalConfig.ASM_COMMENT_OWN_LINE = args.asm_comment_own_line
         if args.comment_offset_width is not None:
             GlobalConfig.ASM_COMMENT_OFFSET_WIDTH = args.comment_offset_width
+        if args.asm_comment_symbol_refs is not None:
+            GlobalConfig.ASM_COMMENT_SYMBOL_REFERENCES = args.asm_comment_symbol_refs
+        if args.asm_comment_frame_size is not None:
+            GlobalConfig.ASM_COMMENT_FRAME_SIZE = args.asm_comment_frame_size
+        if args.asm_comment_cache_ops is not None:
+            GlobalConfig.ASM_COMMENT_CACHE_OPS = args.asm_comment_cache_ops
+        if args.asm_comment_float_constants is not None:
+            GlobalConfig.ASM_COMMENT_FLOAT_CONSTANTS = args.asm_comment_float_constants
+        if args.asm_comment_jtbl_cases is not None:
+            GlobalConfig.ASM_COMMENT_JUMP_TABLE_CASES = args.asm_comment_jtbl_cases
+        if args.asm_comment_func_refs is not None:
+            GlobalConfig.ASM_COMMENT_FUNCTION_REFERENCES = args.asm_comment_func_refs
+        if args.asm_comment_func_refs_max is not None:
+            GlobalConfig.ASM_COMMENT_FUNCTION_REFERENCES_MAX = int(args.asm_comment_func_refs_max, 0)
         if args.glabel_count is not None:
             GlobalConfig.GLABEL_ASM_COUNT = args.glabel_count
 
@@ -221,6 +417,30 @@ class GlobalConfig:
             GlobalConfig.ASM_TEXT_END_LABEL = args.asm_end_label
         if args.asm_func_as_label is not None:
             GlobalConfig.ASM_TEXT_FUNC_AS_LABEL = args.asm_func_as_label
+        if args.asm_string_pools is not None:
+            GlobalConfig.ASM_STRING_POOLS = args.asm_string_pools
+        if args.asm_sll_hints is not None:
+            GlobalConfig.ASM_SLL_HINTS_AS_MNEMONICS = args.asm_sll_hints
+        if args.asm_size_directive is not None:
+            GlobalConfig.ASM_SIZE_DIRECTIVE = args.asm_size_directive
+        if args.asm_data_fill_threshold is not None:
+            GlobalConfig.ASM_DATA_FILL_THRESHOLD = int(args.asm_data_fill_threshold, 0)
+        if args.asm_bss_comm is not None:
+            GlobalConfig.ASM_BSS_AS_COMMON_SYMBOLS = args.asm_bss_comm
+        if args.asm_data_symbols_alignment is not None:
+            GlobalConfig.ASM_DATA_SYMBOLS_ALIGNMENT = int(args.asm_data_symbols_alignment, 0)
+        if args.asm_gp64 is not None:
+            GlobalConfig.ASM_GP_64 = args.asm_gp64
+        if args.asm_pseudo_la is not None:
+            GlobalConfig.ASM_PSEUDO_LA = args.asm_pseudo_la
+        if args.asm_text_set_noat is not None:
+            GlobalConfig.ASM_TEXT_SET_NOAT = args.asm_text_set_noat
+        if args.asm_text_padding_balign is not None:
+            GlobalConfig.ASM_TEXT_PADDING_AS_BALIGN = args.asm_text_padding_balign
+        if args.asm_text_delay_slot_indent is not None:
+            if args.asm_text_delay_slot_indent.strip() != "":
+                raise RuntimeError(f"The delay slot indentation must only contain whitespace, got {args.asm_text_delay_slot_indent!r}")
+            GlobalConfig.ASM_TEXT_DELAY_SLOT_INDENT = args.asm_text_delay_slot_indent
 
         if args.print_new_file_boundaries is not None:
             GlobalConfig.PRINT_NEW_FILE_BOUNDARIES = args.print_new_file_boundaries
@@ -0,0 +1,85 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+from typing import Generic, Generator, TypeVar
+
+from .SortedDict import SortedDict
+
+ValueType = TypeVar("ValueType")
+
+
+class IntervalMap(Generic[ValueType]):
+    """Maps non overlapping [`start`, `end`) ranges of addresses to a value.
+
+    If `mergeOverlapping` is `True`, then inserting a range which overlaps with already existing ranges will merge all of them into a single
+    range using the newly inserted value. Otherwise inserting an overlapping range raises a `RuntimeError`.
+    Adjacent ranges (where the end of one range is the start of the other) are not considered to be overlapping and are never merged."""
+
+    def __init__(self, mergeOverlapping: bool=False):
+        self.mergeOverlapping = mergeOverlapping
+        self.ranges: SortedDict[tuple[int, ValueType]] = SortedDict()
+        "Maps the start of each range to its end and value"
+
+
+    def insertRange(self, start: int, end: int, value: ValueType) -> None:
+        if start >= end:
+            raise RuntimeError(f"Invalid range [0x{start:X}, 0x{end:X})")
+
+        overlapping = list(self.overlapping(start, end))
+        if len(overlapping) > 0:
+            if not self.mergeOverlapping:
+                otherStart, otherEnd, _ = overlapping[0]
+                raise RuntimeError(f"Range [0x{start:X}, 0x{end:X}) overlaps with the already existing range [0x{otherStart:X}, 0x{otherEnd:X})")
+
+            for otherStart, otherEnd, _ in overlapping:
+                start = min(start, otherStart)
+                end = max(end, otherEnd)
+                self.ranges.remove(otherStart)
+
+        self.ranges.add(start, (end, value))
+
+    def getRange(self, address: int) -> tuple[int, int, ValueType]|None:
+        "Returns the `(start, end, value)` range containing `address`, or `None` if no range contains it"
+        pair = self.ranges.getKeyRight(address)
+        if pair is None:
+            return None
+        start, (end, value) = pair
+        if address >= end:
+            return None
+        return start, end, value
+
+    def get(self, address: int) -> ValueType|None:
+        "Returns the value of the range containing `address`, or `None` if no range contains it"
+        interval = self.getRange(address)
+        if interval is None:
+            return None
+        return interval[2]
+
+    def overlapping(self, start: int, end: int) -> Generator[tuple[int, int, ValueType], None, None]:
+        "Generator which iterates over every `(start, end, value)` range which overlaps with [`start`, `end`), sorted by address"
+        previousPair = self.ranges.getKeyRight(start, inclusive=False)
+        if previousPair is not None:
+            otherStart, (otherEnd, value) = previousPair
+            if otherEnd > start:
+                yield otherStart, otherEnd, value
+
+        for otherStart, (otherEnd, value) in self.ranges.getRange(start, end):
+            yield otherStart, otherEnd, value
+
+
+    def __iter__(self) -> Generator[tuple[int, int, ValueType], None, None]:
+        "Iteration is sorted by address"
+        for start, (end, value) in self.ranges.items():
+            yield start, end, value
+
+    def __len__(self) -> int:
+        return len(self.ranges)
+
+    def __contains__(self, address: object) -> bool:
+        if not isinstance(address, int):
+            return False
+        return self.getRange(address) is not None
@@ -0,0 +1,82 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import dataclasses
+
+from .ContextSymbols import ContextSymbol
+from .Context import Context
+
+
+N64EntryVram = 0x80000400
+"Address the CIC bootcode copies the first 1MiB of the game to, and jumps to"
+
+
+# Analysis of the boot stub emitted at the start of the `entry` segment of N64 games.
+# The stub clears the bss, sets up the stack and jumps to the game's boot procedure through a register
+@dataclasses.dataclass
+class N64EntrySeeder:
+    entrypoint: int
+    "Vram of the first instruction of the stub"
+    entrypointSize: int
+    "Size of the stub in bytes, including the delay slot of the jump to the boot procedure"
+    bootproc: int
+    "Vram of the boot procedure of the game, which the stub jumps to"
+
+    @staticmethod
+    def fromWords(words: list[int], vram: int=N64EntryVram, bootVramStart: int|None=None, bootVramEnd: int|None=None) -> N64EntrySeeder|None:
+        """Looks for the handoff of the stub (`jr` to a register loaded with `lui` and `addiu`/`ori`) on the big endian `words` of the `entry` segment.
+
+        If [`bootVramStart`, `bootVramEnd`) is passed then the handoff must land inside that range. Returns `None` if no handoff was found"""
+        registers: dict[int, int] = dict()
+
+        for i, w in enumerate(words):
+            opcode = w >> 26
+            rs = (w >> 21) & 0x1F
+            rt = (w >> 16) & 0x1F
+            imm = w & 0xFFFF
+
+            if opcode == 0x0F: # lui
+                registers[rt] = imm << 16
+            elif opcode == 0x09: # addiu
+                if rs in registers:
+                    signedImm = imm - 0x10000 if imm & 0x8000 else imm
+                    registers[rt] = (registers[rs] + signedImm) & 0xFFFFFFFF
+                else:
+                    registers.pop(rt, None)
+            elif opcode == 0x0D: # ori
+                if rs in registers:
+                    registers[rt] = registers[rs] | imm
+                else:
+                    registers.pop(rt, None)
+            elif opcode == 0x00 and (w & 0x3F) == 0x08 and rs != 31: # jr, but not jr $ra
+                target = registers.get(rs)
+                if target is None:
+                    return None
+                if bootVramStart is not None and bootVramEnd is not None and not (bootVramStart <= target < bootVramEnd):
+                    return None
+                # Include the delay slot
+                return N64EntrySeeder(vram, (i + 2) * 4, target)
+
+        return None
+
+
+    def seedContext(self, context: Context, entrypointName: str="entrypoint", bootprocName: str="bootproc") -> tuple[ContextSymbol, ContextSymbol]:
+        """Adds the stub and the boot procedure as user declared functions of the global segment. Names and sizes already set by the user are kept.
+
+        The size of the stub is set, so the function which follows it in the `entry` segment isn't merged into it"""
+        entrypoint = context.globalSegment.addFunction(self.entrypoint)
+        if entrypoint.name is None:
+            entrypoint.name = entrypointName
+        entrypoint.setSizeIfUnset(self.entrypointSize)
+        entrypoint.isUserDeclared = True
+
+        bootproc = context.globalSegment.addFunction(self.bootproc)
+        if bootproc.name is None:
+            bootproc.name = bootprocName
+        bootproc.isUserDeclared = True
+
+        return entrypoint, bootproc
@@ -0,0 +1,44 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import dataclasses
+import functools
+
+
+@functools.total_ordering
+@dataclasses.dataclass(frozen=True, eq=False)
+class ParentSegmentInfo:
+    """Identifies the segment of the context an element belongs to, so it can be used as a dictionary key or sorted.
+
+    Two infos are equal (and hash the same) if they refer to the same segment: the same overlay category and segment vrom for overlay segments.
+    Every info without an overlay category refers to the global segment, so its `segmentVromStart` is ignored.
+    The global segment sorts before the overlay segments, which are sorted by overlay category and then by vrom"""
+
+    segmentVromStart: int
+    overlayCategory: str|None
+
+    @property
+    def isGlobalSegment(self) -> bool:
+        return self.overlayCategory is None
+
+    def _getKey(self) -> tuple[int, str, int]:
+        if self.overlayCategory is None:
+            return (0, "", 0)
+        return (1, self.overlayCategory, self.segmentVromStart)
+
+    def __eq__(self, other: object) -> bool:
+        if not isinstance(other, ParentSegmentInfo):
+            return NotImplemented
+        return self._getKey() == other._getKey()
+
+    def __lt__(self, other: ParentSegmentInfo) -> bool:
+        if not isinstance(other, ParentSegmentInfo):
+            return NotImplemented
+        return self._getKey() < other._getKey()
+
+    def __hash__(self) -> int:
+        return hash(self._getKey())
@@ -0,0 +1,78 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import dataclasses
+import struct
+
+from .GlobalConfig import InputEndian
+from .ContextSymbols import ContextSymbol
+from .Context import Context
+
+
+PsxExeMagic = b"PS-X EXE"
+
+PsxExeHeaderSize = 0x800
+"The text of the executable starts right after the header"
+
+
+# Header of the PS1 executables (`PS-X EXE`)
+@dataclasses.dataclass
+class PsxExeHeader:
+    initialPc:       int  # word  # 0x10
+    initialGp:       int  # word  # 0x14
+    textAddress:     int  # word  # 0x18
+    textSize:        int  # word  # 0x1C
+    dataAddress:     int  # word  # 0x20
+    dataSize:        int  # word  # 0x24
+    bssAddress:      int  # word  # 0x28
+    bssSize:         int  # word  # 0x2C
+    initialSpBase:   int  # word  # 0x30
+    initialSpOffset: int  # word  # 0x34
+                                  # 0x38
+
+    @staticmethod
+    def fromBytearray(array_of_bytes: bytearray, offset: int = 0) -> PsxExeHeader:
+        magic = bytes(array_of_bytes[offset:offset+len(PsxExeMagic)])
+        if magic != PsxExeMagic:
+            raise RuntimeError(f"Not a PS-X EXE file. Expected magic {PsxExeMagic!r}, got {magic!r}")
+
+        # The header is always little endian
+        headerFormat = "<10I"
+        unpacked = struct.unpack_from(headerFormat, array_of_bytes, 0x10 + offset)
+
+        return PsxExeHeader(*unpacked)
+
+
+    @property
+    def textVromStart(self) -> int:
+        return PsxExeHeaderSize
+
+    @property
+    def textVromEnd(self) -> int:
+        return PsxExeHeaderSize + self.textSize
+
+    @property
+    def vramEnd(self) -> int:
+        "End of the memory used by the executable, including its bss"
+        vramEnd = self.textAddress + self.textSize
+        if self.bssSize > 0:
+            vramEnd = max(vramEnd, self.bssAddress + self.bssSize)
+        return vramEnd
+
+
+    def seedContext(self, context: Context, entrypointName: str="__start") -> ContextSymbol:
+        """Sets the ranges of the global segment of `context` to match this executable, mapping the file offset `0x800` to the text address.
+
+        The segment is marked as little endian. Returns the entrypoint, added as a user declared function named `entrypointName`.
+        The `initialGp` is not applied to `GlobalConfig.GP_VALUE`, since most PS1 executables don't use gp-relative accesses"""
+        context.globalSegment.changeRanges(self.textVromStart, self.textVromEnd, self.textAddress, self.vramEnd)
+        context.globalSegment.endian = InputEndian.LITTLE
+
+        entrypoint = context.globalSegment.addFunction(self.initialPc)
+        entrypoint.name = entrypointName
+        entrypoint.isUserDeclared = True
+        return entrypoint
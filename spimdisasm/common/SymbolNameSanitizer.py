@@ -0,0 +1,64 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+
+class SymbolNameSanitizer:
+    """Maps symbol names to identifiers the assembler accepts (`[A-Za-z_][A-Za-z0-9_]*`).
+
+    Every byte of the UTF-8 encoding of an illegal character is replaced with `_XX_`, where `XX` is its uppercase hexadecimal value,
+    and a leading digit is escaped the same way. A literal `_` which would be mistaken for the start of an escape sequence is escaped too,
+    so the mapping is injective and doesn't depend on which other names were sanitized. `unsanitize` reverts any name produced by `sanitize`"""
+
+    HexDigits = "0123456789ABCDEF"
+
+    @staticmethod
+    def isLegalChar(char: str, isFirst: bool) -> bool:
+        if char == "_" or ("a" <= char <= "z") or ("A" <= char <= "Z"):
+            return True
+        return not isFirst and "0" <= char <= "9"
+
+    @staticmethod
+    def _escape(char: str) -> str:
+        return "".join(f"_{byte:02X}_" for byte in char.encode("utf-8"))
+
+    def _isAmbiguousUnderscore(self, name: str, i: int) -> bool:
+        "Checks if the literal `_` at `i` would be followed by `XX_` on the sanitized name"
+        if i + 3 >= len(name):
+            return False
+        if name[i+1] not in self.HexDigits or name[i+2] not in self.HexDigits:
+            return False
+        # Escaped characters start with `_` too
+        return name[i+3] == "_" or not self.isLegalChar(name[i+3], False)
+
+    def sanitize(self, name: str) -> str:
+        sanitized = ""
+        for i, char in enumerate(name):
+            if not self.isLegalChar(char, i == 0) or (char == "_" and self._isAmbiguousUnderscore(name, i)):
+                sanitized += self._escape(char)
+            else:
+                sanitized += char
+        return sanitized
+
+    def unsanitize(self, sanitized: str) -> str|None:
+        "Returns the original name of a name produced by `sanitize`, or `None` if `sanitize` can't produce it"
+        decoded = bytearray()
+        i = 0
+        while i < len(sanitized):
+            if sanitized[i] == "_" and i + 3 < len(sanitized) and sanitized[i+1] in self.HexDigits and sanitized[i+2] in self.HexDigits and sanitized[i+3] == "_":
+                decoded.append(int(sanitized[i+1:i+3], 16))
+                i += 4
+            else:
+                decoded += sanitized[i].encode("utf-8")
+                i += 1
+
+        try:
+            name = decoded.decode("utf-8")
+        except UnicodeDecodeError:
+            return None
+        if self.sanitize(name) != sanitized:
+            return None
+        return name
@@ -6,18 +6,21 @@
 from __future__ import annotations
 
 import ast
-from typing import TextIO, Generator
+from typing import TextIO, Generator, TYPE_CHECKING
 import os
 
 from . import Utils
 from .SortedDict import SortedDict
-from .GlobalConfig import GlobalConfig
+from .GlobalConfig import GlobalConfig, InputEndian
 from .FileSectionType import FileSectionType
 from .ContextSymbols import SymbolSpecialType, ContextSymbol
 
+if TYPE_CHECKING:
+    from .Context import Context
+
 
 class SymbolsSegment:
-    def __init__(self, vromStart: int|None, vromEnd: int|None, vramStart: int, vramEnd: int, overlayCategory: str|None=None):
+    def __init__(self, vromStart: int|None, vromEnd: int|None, vramStart: int, vramEnd: int, overlayCategory: str|None=None, endian: InputEndian|None=None, isolated: bool=False, context: Context|None=None):
         assert vramStart < vramEnd
         if vromStart is not None and vromEnd is not None:
             assert vromStart < vromEnd
@@ -28,8 +31,20 @@ class SymbolsSegment:
         self.vramStart: int = vramStart
         self.vramEnd: int = vramEnd
 
+        self.bssVramRanges: list[tuple[int, int]] = list()
+        "Vram ranges of the bss sections of this segment which lie outside [`vramStart`, `vramEnd`). Those are part of the segment too, but don't change its declared vram range"
+
         self.overlayCategory: str|None = overlayCategory
 
+        self.endian: InputEndian|None = endian
+        "Endianness of the files belonging to this segment. If `None` then `GlobalConfig.ENDIAN` is used instead"
+
+        self.isolated: bool = isolated
+        "References from this segment are only resolved to symbols of this segment and the global segment, never to the ones of other overlays"
+
+        self.context: Context|None = context
+        "The context this segment belongs to, which holds the settings shared by every segment, like the autogenerated names callback or the symbol name sanitizer"
+
         self.symbols: SortedDict[ContextSymbol] = SortedDict()
 
         self.constants: dict[int, ContextSymbol] = dict()
@@ -60,6 +75,12 @@ class SymbolsSegment:
         return self.vramEnd - self.vramStart
 
 
+    def getEndian(self) -> InputEndian:
+        if self.endian is not None:
+            return self.endian
+        return GlobalConfig.ENDIAN
+
+
     def isVromInRange(self, vrom: int) -> bool:
         if self.vromStart is None:
             return False
@@ -68,7 +89,9 @@ class SymbolsSegment:
         return self.vromStart <= vrom < self.vromEnd
 
     def isVramInRange(self, vram: int) -> bool:
-        return self.vramStart <= vram < self.vramEnd
+        if self.vramStart <= vram < self.vramEnd:
+            return True
+        return any(bssVramStart <= vram < bssVramEnd for bssVramStart, bssVramEnd in self.bssVramRanges)
 
     def changeRanges(self, vromStart: int, vromEnd: int, vramStart: int, vramEnd: int) -> None:
         assert vromStart < vromEnd
@@ -81,6 +104,14 @@ class SymbolsSegment:
         self.vramEnd = vramEnd
 
 
+    def addBssVramRange(self, vramStart: int, vramEnd: int) -> None:
+        "Makes [`vramStart`, `vramEnd`) part of this segment as bss, without changing the declared vram range"
+        if self.vramStart <= vramStart and vramEnd <= self.vramEnd:
+            return
+        if (vramStart, vramEnd) not in self.bssVramRanges:
+            self.bssVramRanges.append((vramStart, vramEnd))
+
+
     def vromToVram(self, vrom: int) -> int|None:
         if self.vromStart is None:
             return None
@@ -91,6 +122,7 @@ class SymbolsSegment:
         contextSym = self.symbols.get(address, None)
         if contextSym is None:
             contextSym = ContextSymbol(address)
+            contextSym.parentSegment = self
             contextSym.isAutogenerated = isAutogenerated
             contextSym.sectionType = sectionType
             contextSym.overlayCategory = self.overlayCategory
@@ -109,14 +141,14 @@ class SymbolsSegment:
 
     def addFunction(self, address: int, isAutogenerated: bool=False, vromAddress: int|None=None) -> ContextSymbol:
         contextSym = self.addSymbol(address, sectionType=FileSectionType.Text, isAutogenerated=isAutogenerated, vromAddress=vromAddress)
-        if contextSym.type != SymbolSpecialType.jumptablelabel:
+        if contextSym.type != SymbolSpecialType.jumptablelabel and contextSym.type != SymbolSpecialType.altentry:
             contextSym.type = SymbolSpecialType.function
         contextSym.sectionType = FileSectionType.Text
         return contextSym
 
     def addBranchLabel(self, address: int, isAutogenerated: bool=False, vromAddress: int|None=None) -> ContextSymbol:
         contextSym = self.addSymbol(address, sectionType=FileSectionType.Text, isAutogenerated=isAutogenerated, vromAddress=vromAddress)
-        if contextSym.type != SymbolSpecialType.jumptablelabel and contextSym.type != SymbolSpecialType.function:
+        if contextSym.type not in {SymbolSpecialType.jumptablelabel, SymbolSpecialType.function, SymbolSpecialType.altentry}:
             contextSym.type = SymbolSpecialType.branchlabel
         return contextSym
 
@@ -126,6 +158,12 @@ class SymbolsSegment:
             contextSym.type = SymbolSpecialType.jumptable
         return contextSym
 
+    def addAltEntry(self, address: int, isAutogenerated: bool=False, vromAddress: int|None=None) -> ContextSymbol:
+        contextSym = self.addSymbol(address, sectionType=FileSectionType.Text, isAutogenerated=isAutogenerated, vromAddress=vromAddress)
+        contextSym.type = SymbolSpecialType.altentry
+        contextSym.sectionType = FileSectionType.Text
+        return contextSym
+
     def addJumpTableLabel(self, address: int, isAutogenerated: bool=False, vromAddress: int|None=None) -> ContextSymbol:
         contextSym = self.addSymbol(address, sectionType=FileSectionType.Text, isAutogenerated=isAutogenerated, vromAddress=vromAddress)
         contextSym.type = SymbolSpecialType.jumptablelabel
@@ -136,6 +174,7 @@ class SymbolsSegment:
     def addConstant(self, constantValue: int, name: str) -> ContextSymbol:
         if constantValue not in self.constants:
             contextSym = ContextSymbol(constantValue)
+            contextSym.parentSegment = self
             contextSym.name = name
             contextSym.type = SymbolSpecialType.constant
             self.constants[constantValue] = contextSym
@@ -160,6 +199,20 @@ class SymbolsSegment:
     def getSymbolsRange(self, addressStart: int, addressEnd: int) -> Generator[tuple[int, ContextSymbol], None, None]:
         return self.symbols.getRange(addressStart, addressEnd, startInclusive=True, endInclusive=False)
 
+    def getSymbolsInRange(self, addressStart: int, addressEnd: int, includeOverlapping: bool=False) -> Generator[ContextSymbol, None, None]:
+        """Generator which iterates, in ascending address order, every symbol which starts in the [`addressStart`, `addressEnd`) range.
+
+        If `includeOverlapping` is `True` then the symbol which starts before `addressStart` is also yielded if its size extends into the range."""
+        if includeOverlapping:
+            pair = self.symbols.getKeyRight(addressStart, inclusive=False)
+            if pair is not None:
+                symVram, contextSym = pair
+                if symVram + contextSym.getSize() > addressStart:
+                    yield contextSym
+
+        for _, contextSym in self.symbols.getRange(addressStart, addressEnd, startInclusive=True, endInclusive=False):
+            yield contextSym
+
     def getConstant(self, constantValue: int) -> ContextSymbol|None:
         return self.constants.get(constantValue, None)
 
@@ -363,8 +416,13 @@ class SymbolsSegment:
 
             vram = int(vramStr, 16)
             varSize = int(varSizeStr, 16)
+            arrayCount: int|None = None
             if varType == "":
                 varType = None
+            elif varType is not None and varType.endswith("]") and "[" in varType:
+                # Arrays are declared as `type[count]`
+                varType, arrayCountStr = varType[:-1].split("[", 1)
+                arrayCount = int(arrayCountStr, 0)
 
             specialType = SymbolSpecialType.fromStr(varType)
             if specialType is not None:
@@ -377,6 +435,8 @@ class SymbolsSegment:
                     contextSym = self.addJumpTable(vram)
                 elif specialType == SymbolSpecialType.jumptablelabel:
                     contextSym = self.addJumpTableLabel(vram)
+                elif specialType == SymbolSpecialType.altentry:
+                    contextSym = self.addAltEntry(vram)
                 elif specialType == SymbolSpecialType.hardwarereg:
                     contextSym = self.addSymbol(vram)
                 else:
@@ -386,6 +446,7 @@ class SymbolsSegment:
 
             contextSym.name = varName
             contextSym.type = varType
+            contextSym.arrayCount = arrayCount
             contextSym.size = varSize
             contextSym.isUserDeclared = True
 
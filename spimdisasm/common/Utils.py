@@ -6,17 +6,26 @@
 from __future__ import annotations
 
 import argparse
+import codecs
+import contextlib
 import csv
 import os
 import hashlib
 import json
+import math
+import mmap
 import struct
 import subprocess
 import sys
+from typing import Iterator, Union
 
 from .GlobalConfig import GlobalConfig, InputEndian
 
 
+BytesBuffer = Union[bytearray, mmap.mmap]
+"Writable input accepted by the sections. Slicing it produces `bytes` for a `mmap.mmap`, so only use the buffer protocol, `len` and slices converted with `bytes`"
+
+
 def eprint(*args, **kwargs):
     print(*args, file=sys.stderr, **kwargs)
 
@@ -55,6 +64,22 @@ def readFileAsBytearray(filepath: str) -> bytearray:
     with open(filepath, mode="rb") as f:
         return bytearray(f.read())
 
+@contextlib.contextmanager
+def mapFile(filepath: str) -> Iterator[BytesBuffer]:
+    """Maps the file to memory instead of reading it, so only the pages of the byte ranges actually used are loaded. Useful for huge files, like disc images.
+
+    The mapping is copy-on-write, so in-place conversions (like the middle endian one) never modify the file. It is closed when the `with` block ends,
+    so every section must be created inside of it. Sections copy what they need, so they can be used after that"""
+    if not os.path.exists(filepath) or os.path.getsize(filepath) == 0:
+        yield bytearray(0)
+        return
+    with open(filepath, mode="rb") as f:
+        mapping = mmap.mmap(f.fileno(), 0, access=mmap.ACCESS_COPY)
+    try:
+        yield mapping
+    finally:
+        mapping.close()
+
 def readFile(filepath: str) -> list[str]:
     with open(filepath) as f:
         return [x.strip() for x in f.readlines()]
@@ -66,7 +91,7 @@ def readJson(filepath):
 def removeExtraWhitespace(line: str) -> str:
     return " ".join(line.split())
 
-def bytesToBEWords(array_of_bytes: bytearray, offset: int=0, offsetEnd: int|None=None) -> list[int]:
+def bytesToBEWords(array_of_bytes: BytesBuffer, offset: int=0, offsetEnd: int|None=None, endian: InputEndian|None=None) -> list[int]:
     totalBytesCount = len(array_of_bytes)
     if totalBytesCount == 0:
         return list()
@@ -76,7 +101,10 @@ def bytesToBEWords(array_of_bytes: bytearray, offset: int=0, offsetEnd: int|None
         bytesCount = offsetEnd
     bytesCount -= offset
 
-    if GlobalConfig.ENDIAN == InputEndian.MIDDLE:
+    if endian is None:
+        endian = GlobalConfig.ENDIAN
+
+    if endian == InputEndian.MIDDLE:
         # Convert middle endian to big endian
         halfwords = bytesCount//2
         little_byte_format = f"<{halfwords}H"
@@ -86,10 +114,39 @@ def bytesToBEWords(array_of_bytes: bytearray, offset: int=0, offsetEnd: int|None
 
     words = bytesCount//4
     endian_format = f">{words}I"
-    if GlobalConfig.ENDIAN == InputEndian.LITTLE:
+    if endian == InputEndian.LITTLE:
         endian_format = f"<{words}I"
     return list(struct.unpack_from(endian_format, array_of_bytes, offset))
 
+def detectEndian(array_of_bytes: bytearray) -> InputEndian|None:
+    """Guesses the endianness of a N64 rom by looking at the first word of its header.
+
+    Returns `None` if the magic doesn't match any of the known endiannesses"""
+    if len(array_of_bytes) < 4:
+        return None
+    magic = struct.unpack_from(">I", array_of_bytes, 0)[0]
+    if magic == 0x80371240:
+        return InputEndian.BIG
+    if magic == 0x40123780:
+        return InputEndian.LITTLE
+    if magic == 0x37804012:
+        return InputEndian.MIDDLE
+    return None
+
+def byteswapToBigEndian(array_of_bytes: bytearray, endian: InputEndian) -> bytearray:
+    "Returns a big endian copy of `array_of_bytes`, which is assumed to be in the `endian` byte order"
+    result = bytearray(array_of_bytes)
+    if endian == InputEndian.BIG:
+        return result
+
+    if endian == InputEndian.MIDDLE:
+        halfwords = len(result)//2
+        struct.pack_into(f">{halfwords}H", result, 0, *struct.unpack_from(f"<{halfwords}H", result, 0))
+    else:
+        words = len(result)//4
+        struct.pack_into(f">{words}I", result, 0, *struct.unpack_from(f"<{words}I", result, 0))
+    return result
+
 def beWordsToBytes(words_list: list[int], buffer: bytearray) -> bytearray:
     words = len(words_list)
     big_endian_format = f">{words}I"
@@ -102,11 +159,38 @@ def wordToFloat(word: int) -> float:
 def qwordToDouble(qword: int) -> float:
     return struct.unpack('>d', struct.pack('>Q', qword))[0]
 
-def beWordToCurrenEndian(word: int) -> int:
-    if GlobalConfig.ENDIAN == InputEndian.BIG:
+def floatToWord(value: float) -> int:
+    return struct.unpack('>I', struct.pack('>f', value))[0]
+
+def doubleToQword(value: float) -> int:
+    return struct.unpack('>Q', struct.pack('>d', value))[0]
+
+def wordToFloatStr(word: int) -> str|None:
+    "Returns the decimal representation of the float `word`, or `None` if it isn't finite or if parsing that representation back doesn't produce exactly the same bits"
+    if not math.isfinite(wordToFloat(word)):
+        return None
+    value = str(wordToFloat(word))
+    if floatToWord(float(value)) != word:
+        return None
+    return value
+
+def qwordToDoubleStr(qword: int) -> str|None:
+    "Returns the decimal representation of the double `qword`, or `None` if it isn't finite or if parsing that representation back doesn't produce exactly the same bits"
+    if not math.isfinite(qwordToDouble(qword)):
+        return None
+    value = str(qwordToDouble(qword))
+    if doubleToQword(float(value)) != qword:
+        return None
+    return value
+
+def beWordToCurrenEndian(word: int, endian: InputEndian|None=None) -> int:
+    if endian is None:
+        endian = GlobalConfig.ENDIAN
+
+    if endian == InputEndian.BIG:
         return word
 
-    if GlobalConfig.ENDIAN == InputEndian.LITTLE:
+    if endian == InputEndian.LITTLE:
         return struct.unpack('<I', struct.pack('>I', word))[0]
 
     # MIDDLE
@@ -131,7 +215,8 @@ def readCsv(filepath: str) -> list[list[str]]:
 
     return data
 
-def decodeString(buf: bytearray, offset: int) -> tuple[str, int]:
+def decodeString(buf: bytearray, offset: int, encoding: str|None=None, allowUnterminated: bool=False) -> tuple[str, int]:
+    "If `allowUnterminated` is `True` then reaching the end of `buf` without finding a NUL terminator is not an error, and the string lasts until the end of `buf`"
     # Escape characters that are unlikely to be used
     bannedEscapeCharacters = [
         0x01,
@@ -171,7 +256,7 @@ def decodeString(buf: bytearray, offset: int) -> tuple[str, int]:
     while offset + i < len(buf) and buf[offset + i] != 0:
         dst.append(buf[offset + i])
         i += 1
-    if offset + i > len(buf):
+    if offset + i >= len(buf) and not allowUnterminated:
         # We reached the end of the buffer without reaching a 0.
         raise RuntimeError()
 
@@ -179,7 +264,17 @@ def decodeString(buf: bytearray, offset: int) -> tuple[str, int]:
         if bannedChar in dst:
             raise RuntimeError()
 
-    result = dst.decode("EUC-JP").replace("\n", "\\n").replace("\r", "\\r").replace("\t", "\\t").replace('"', '\\"').replace("\f", "\\f").replace("\a", "\\a").replace("\x1B", "\\x1B")
+    if encoding is None:
+        encoding = GlobalConfig.STRING_ENCODING
+
+    if GlobalConfig.STRING_ASCII_STRICT and codecs.lookup(encoding).name == "ascii":
+        # '\a', '\t', '\n', '\f', '\r' and the escape character
+        allowedControlCharacters = {0x07, 0x09, 0x0A, 0x0C, 0x0D, 0x1B}
+        for char in dst:
+            if not (0x20 <= char <= 0x7E) and char not in allowedControlCharacters:
+                raise RuntimeError()
+
+    result = dst.decode(encoding).replace("\n", "\\n").replace("\r", "\\r").replace("\t", "\\t").replace('"', '\\"').replace("\f", "\\f").replace("\a", "\\a").replace("\x1B", "\\x1B")
     return result, i
 
 
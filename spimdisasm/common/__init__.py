@@ -4,10 +4,15 @@
 from . import Utils
 
 from .SortedDict import SortedDict
-from .GlobalConfig import GlobalConfig, InputEndian, Compiler
+from .IntervalMap import IntervalMap
+from .GlobalConfig import GlobalConfig, InputEndian, Compiler, Abi
+from .SymbolNameSanitizer import SymbolNameSanitizer
 from .FileSectionType import FileSectionType, FileSections_ListBasic, FileSections_ListAll
-from .ContextSymbols import SymbolSpecialType, ContextSymbol, ContextOffsetSymbol, ContextRelocSymbol
+from .ContextSymbols import SymbolSpecialType, SymbolOrigin, ContextSymbol, ContextOffsetSymbol, ContextRelocSymbol
 from .SymbolsSegment import SymbolsSegment
-from .Context import Context
+from .ParentSegmentInfo import ParentSegmentInfo
+from .Context import Context, ContextSnapshot, InferredType
+from .PsxExeHeader import PsxExeHeader
+from .N64EntrySeeder import N64EntrySeeder
 from .FileSplitFormat import FileSplitFormat, FileSplitEntry
 from .ElementBase import ElementBase
@@ -5,6 +5,8 @@
 
 from __future__ import annotations
 
+import struct
+
 from .. import common
 
 from .Elf32Constants import Elf32SectionHeaderType
@@ -13,6 +15,7 @@ from .Elf32SectionHeaders import Elf32SectionHeaders
 from .Elf32StringTable import Elf32StringTable
 from .Elf32Syms import Elf32Syms
 from .Elf32Rels import Elf32Rels
+from .Elf32Mdebug import Elf32Mdebug
 
 
 class Elf32File:
@@ -26,8 +29,14 @@ class Elf32File:
         self.progbits: dict[common.FileSectionType, bytearray] = dict()
         self.nobits: int | None = None
 
+        self.mipsSpecialSections: dict[common.FileSectionType, bytearray] = dict()
+        "Raw contents of the `.reginfo` and `.MIPS.abiflags` sections"
+
         self.rel: dict[common.FileSectionType, Elf32Rels] = dict()
 
+        self.mdebug: Elf32Mdebug | None = None
+        "Symbolic debugging information, if the `.mdebug` section is present. Use `Elf32Mdebug.seedContext` to import its symbols"
+
         self.sectionHeaders = Elf32SectionHeaders(array_of_bytes, self.header.shoff, self.header.shnum)
 
         shstrtabSectionEntry = self.sectionHeaders.sections[self.header.shstrndx]
@@ -102,16 +111,22 @@ class Elf32File:
                 # ?
                 pass
             elif entry.type == Elf32SectionHeaderType.MIPS_DEBUG.value:
-                # ?
-                pass
+                try:
+                    self.mdebug = Elf32Mdebug(array_of_bytes, entry.offset, entry.size)
+                    common.Utils.printVerbose(sectionEntryName, "symbols: ", len(self.mdebug.symbols))
+                    common.Utils.printVerbose()
+                except (RuntimeError, struct.error) as e:
+                    common.Utils.eprint("Unable to parse .mdebug: ", e)
             elif entry.type == Elf32SectionHeaderType.MIPS_REGINFO.value:
-                # ?
-                pass
+                self.mipsSpecialSections[common.FileSectionType.Reginfo] = array_of_bytes[entry.offset:entry.offset+entry.size]
+                common.Utils.printVerbose(sectionEntryName, "size: ", entry.size)
+                common.Utils.printVerbose()
             elif entry.type == Elf32SectionHeaderType.MIPS_OPTIONS.value:
                 # ?
                 pass
             elif entry.type == Elf32SectionHeaderType.MIPS_ABIFLAGS.value:
-                # ?
-                pass
+                self.mipsSpecialSections[common.FileSectionType.AbiFlags] = array_of_bytes[entry.offset:entry.offset+entry.size]
+                common.Utils.printVerbose(sectionEntryName, "size: ", entry.size)
+                common.Utils.printVerbose()
             else:
                 common.Utils.eprint("Unknown section header type found:", sectionEntryName, entry)
@@ -0,0 +1,316 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import dataclasses
+import struct
+
+from .. import common
+
+
+MdebugMagic = 0x7009
+
+MdebugSymType_Global     =  1
+MdebugSymType_Static     =  2
+MdebugSymType_Block      =  7
+MdebugSymType_End        =  8
+MdebugSymType_File       = 11
+MdebugSymType_Proc       =  6
+MdebugSymType_StaticProc = 14
+
+MdebugDataStorageClasses = {
+    2,  # scData
+    3,  # scBss
+    13, # scSData
+    14, # scSBss
+    15, # scRData
+    17, # scCommon
+    18, # scSCommon
+    27, # scRConst
+}
+
+MdebugTypeQualifier_Array = 3
+
+MdebugBasicType_Char = 2
+
+MdebugBasicTypes: dict[int, tuple[str, int]] = {
+    2:  ("s8",   1),    # btChar, only arrays of it are strings
+    3:  ("u8",   1),    # btUChar
+    4:  ("s16",  2),    # btShort
+    5:  ("u16",  2),    # btUShort
+    6:  ("s32",  4),    # btInt
+    7:  ("u32",  4),    # btUInt
+    8:  ("s32",  4),    # btLong
+    9:  ("u32",  4),    # btULong
+    10: ("f32",  4),    # btFloat
+    11: ("f64",  8),    # btDouble
+}
+"Types and sizes of the basic ECOFF types which can be represented by the disassembler. Other types (structs, unions, pointers, etc) are left unset"
+
+
+# a.k.a. HDRR (symbolic header)
+@dataclasses.dataclass
+class Elf32MdebugHeader:
+    magic:          int  # half  # 0x00
+    vstamp:         int  # half  # 0x02
+    ilineMax:       int  # word  # 0x04
+    cbLine:         int  # word  # 0x08
+    cbLineOffset:   int  # word  # 0x0C
+    idnMax:         int  # word  # 0x10
+    cbDnOffset:     int  # word  # 0x14
+    ipdMax:         int  # word  # 0x18
+    cbPdOffset:     int  # word  # 0x1C
+    isymMax:        int  # word  # 0x20
+    cbSymOffset:    int  # word  # 0x24
+    ioptMax:        int  # word  # 0x28
+    cbOptOffset:    int  # word  # 0x2C
+    iauxMax:        int  # word  # 0x30
+    cbAuxOffset:    int  # word  # 0x34
+    issMax:         int  # word  # 0x38
+    cbSsOffset:     int  # word  # 0x3C
+    issExtMax:      int  # word  # 0x40
+    cbSsExtOffset:  int  # word  # 0x44
+    ifdMax:         int  # word  # 0x48
+    cbFdOffset:     int  # word  # 0x4C
+    crfd:           int  # word  # 0x50
+    cbRfdOffset:    int  # word  # 0x54
+    iextMax:        int  # word  # 0x58
+    cbExtOffset:    int  # word  # 0x5C
+                                 # 0x60
+
+    @staticmethod
+    def fromBytearray(array_of_bytes: bytearray, offset: int = 0) -> Elf32MdebugHeader:
+        headerFormat = ">HH23I"
+        unpacked = struct.unpack_from(headerFormat, array_of_bytes, offset)
+
+        return Elf32MdebugHeader(*unpacked)
+
+
+# a.k.a. FDR (file descriptor)
+@dataclasses.dataclass
+class Elf32MdebugFileDescriptor:
+    adr:            int  # address  # 0x00
+    rss:            int  # word     # 0x04
+    issBase:        int  # word     # 0x08
+    cbSs:           int  # word     # 0x0C
+    isymBase:       int  # word     # 0x10
+    csym:           int  # word     # 0x14
+    ilineBase:      int  # word     # 0x18
+    cline:          int  # word     # 0x1C
+    ioptBase:       int  # word     # 0x20
+    copt:           int  # word     # 0x24
+    ipdFirst:       int  # half     # 0x28
+    cpd:            int  # half     # 0x2A
+    iauxBase:       int  # word     # 0x2C
+    caux:           int  # word     # 0x30
+    rfdBase:        int  # word     # 0x34
+    crfd:           int  # word     # 0x38
+    bitfield:       int  # word     # 0x3C
+    cbLineOffset:   int  # word     # 0x40
+    cbLine:         int  # word     # 0x44
+                                    # 0x48
+
+    @staticmethod
+    def fromBytearray(array_of_bytes: bytearray, offset: int = 0) -> Elf32MdebugFileDescriptor:
+        entryFormat = ">10IHH7I"
+        unpacked = struct.unpack_from(entryFormat, array_of_bytes, offset)
+
+        return Elf32MdebugFileDescriptor(*unpacked)
+
+
+# a.k.a. SYMR (symbol)
+@dataclasses.dataclass
+class Elf32MdebugSymEntry:
+    iss:    int  # word     # 0x00
+    value:  int  # address  # 0x04
+    info:   int  # word     # 0x08
+                            # 0x0C
+
+    @property
+    def st(self) -> int:
+        return (self.info >> 26) & 0x3F
+
+    @property
+    def sc(self) -> int:
+        return (self.info >> 21) & 0x1F
+
+    @property
+    def index(self) -> int:
+        return self.info & 0xFFFFF
+
+    @staticmethod
+    def fromBytearray(array_of_bytes: bytearray, offset: int = 0) -> Elf32MdebugSymEntry:
+        entryFormat = ">III"
+        unpacked = struct.unpack_from(entryFormat, array_of_bytes, offset)
+
+        return Elf32MdebugSymEntry(*unpacked)
+
+
+@dataclasses.dataclass
+class Elf32MdebugSymbolInfo:
+    name: str
+    vram: int
+    isFunction: bool
+    size: int|None = None
+    type: str|None = None
+
+
+# a.k.a. .mdebug (ECOFF symbolic debugging information)
+class Elf32Mdebug:
+    """Parses the procedures and the global and static variables described by the `.mdebug` section emitted by IDO and old GCC versions.
+
+    Symbols are read from the per-file local symbol tables. The size of every procedure is taken from its matching `stEnd` symbol,
+    while the size and type of variables are only known if their type is a basic type or an array of them"""
+
+    def __init__(self, array_of_bytes: bytearray, offset: int, rawSize: int):
+        self.offset: int = offset
+        self.rawSize: int = rawSize
+
+        self.header = Elf32MdebugHeader.fromBytearray(array_of_bytes, offset)
+        if self.header.magic != MdebugMagic:
+            raise RuntimeError(f"Invalid .mdebug magic. Expected 0x{MdebugMagic:04X}, got 0x{self.header.magic:04X}")
+
+        self.fileDescriptors: list[Elf32MdebugFileDescriptor] = list()
+        for i in range(self.header.ifdMax):
+            self.fileDescriptors.append(Elf32MdebugFileDescriptor.fromBytearray(array_of_bytes, self.header.cbFdOffset + i*0x48))
+
+        self.symbols: list[Elf32MdebugSymbolInfo] = list()
+        "Procedures and variables found, in the order they appear on the section"
+
+        for fd in self.fileDescriptors:
+            self._parseFileDescriptor(array_of_bytes, fd)
+
+
+    def _readString(self, array_of_bytes: bytearray, offset: int) -> str:
+        end = array_of_bytes.find(b"\0", offset)
+        if end < 0:
+            end = len(array_of_bytes)
+        return array_of_bytes[offset:end].decode("latin1")
+
+    def _readAux(self, array_of_bytes: bytearray, index: int) -> int|None:
+        if index < 0 or index >= self.header.iauxMax:
+            return None
+        return struct.unpack_from(">I", array_of_bytes, self.header.cbAuxOffset + index*4)[0]
+
+    def _getVariableSizeAndType(self, array_of_bytes: bytearray, fd: Elf32MdebugFileDescriptor, sym: Elf32MdebugSymEntry) -> tuple[int|None, str|None]:
+        # index of a variable points to the TIR of its type on the aux table of its file
+        if sym.index == 0xFFFFF:
+            return None, None
+        auxIndex = fd.iauxBase + sym.index
+        tir = self._readAux(array_of_bytes, auxIndex)
+        if tir is None:
+            return None, None
+
+        isBitfield = (tir >> 31) & 0x1
+        isContinued = (tir >> 30) & 0x1
+        basicType = (tir >> 24) & 0x3F
+        # tq0 to tq3, followed by tq4 and tq5
+        qualifiers = [(tir >> 12) & 0xF, (tir >> 8) & 0xF, (tir >> 4) & 0xF, tir & 0xF, (tir >> 20) & 0xF, (tir >> 16) & 0xF]
+        if isBitfield or isContinued:
+            return None, None
+
+        basicTypeInfo = MdebugBasicTypes.get(basicType)
+        varType = basicTypeInfo[0] if basicTypeInfo is not None else None
+        qualifiers = [tq for tq in qualifiers if tq != 0]
+        if len(qualifiers) == 0:
+            if basicTypeInfo is None:
+                return None, None
+            return basicTypeInfo[1], varType
+
+        if any(tq != MdebugTypeQualifier_Array for tq in qualifiers):
+            # Pointers, functions, etc
+            return None, None
+
+        # Each array qualifier is followed by rndx, dnLow, dnHigh and width (in bits) of its elements
+        elementCount = 1
+        width = 0
+        auxIndex += 1
+        for _ in qualifiers:
+            rndx = self._readAux(array_of_bytes, auxIndex)
+            if rndx is None:
+                return None, None
+            if (rndx >> 20) == 0xFFF:
+                # Escaped file index, stored on the next aux entry
+                auxIndex += 1
+            dnLow = self._readAux(array_of_bytes, auxIndex + 1)
+            dnHigh = self._readAux(array_of_bytes, auxIndex + 2)
+            dimWidth = self._readAux(array_of_bytes, auxIndex + 3)
+            if dnLow is None or dnHigh is None or dimWidth is None or dnHigh < dnLow:
+                return None, None
+            elementCount *= dnHigh - dnLow + 1
+            width = dimWidth
+            auxIndex += 4
+
+        if width == 0 or width % 8 != 0:
+            return None, None
+        size = elementCount * (width // 8)
+        if len(qualifiers) > 1:
+            # Multidimensional array, only the total size is known
+            varType = None
+        elif basicType == MdebugBasicType_Char:
+            varType = "char"
+        return size, varType
+
+    def _parseFileDescriptor(self, array_of_bytes: bytearray, fd: Elf32MdebugFileDescriptor):
+        # Stack of the scopes opened by stProc, stStaticProc, stBlock and stFile, closed by stEnd
+        openScopes: list[Elf32MdebugSymbolInfo|None] = list()
+
+        for i in range(fd.csym):
+            symIndex = fd.isymBase + i
+            if symIndex >= self.header.isymMax:
+                break
+            sym = Elf32MdebugSymEntry.fromBytearray(array_of_bytes, self.header.cbSymOffset + symIndex*0x0C)
+
+            if sym.st in {MdebugSymType_Proc, MdebugSymType_StaticProc}:
+                name = self._readString(array_of_bytes, self.header.cbSsOffset + fd.issBase + sym.iss)
+                procInfo = Elf32MdebugSymbolInfo(name, sym.value, True)
+                openScopes.append(procInfo)
+                if name != "":
+                    self.symbols.append(procInfo)
+            elif sym.st in {MdebugSymType_Block, MdebugSymType_File}:
+                openScopes.append(None)
+            elif sym.st == MdebugSymType_End:
+                if len(openScopes) == 0:
+                    continue
+                scope = openScopes.pop()
+                if scope is not None and scope.isFunction and sym.value > 0:
+                    # The value of the stEnd of a procedure is its size in bytes
+                    scope.size = sym.value
+            elif sym.st in {MdebugSymType_Global, MdebugSymType_Static}:
+                if sym.sc not in MdebugDataStorageClasses:
+                    continue
+                if any(scope is not None and scope.isFunction for scope in openScopes):
+                    # Function-scoped static variables may clash in name with other ones
+                    continue
+                name = self._readString(array_of_bytes, self.header.cbSsOffset + fd.issBase + sym.iss)
+                if name == "":
+                    continue
+                size, varType = self._getVariableSizeAndType(array_of_bytes, fd, sym)
+                self.symbols.append(Elf32MdebugSymbolInfo(name, sym.value, False, size, varType))
+
+
+    def seedContext(self, context: common.Context) -> int:
+        """Adds the procedures and variables of this `.mdebug` to the global segment of `context`, including their sizes and types when known.
+
+        Names, sizes and types already set are kept. Symbols outside the global segment are skipped. Returns the number of symbols added"""
+        count = 0
+        for info in self.symbols:
+            if not context.globalSegment.isVramInRange(info.vram):
+                continue
+
+            if info.isFunction:
+                contextSym = context.globalSegment.addFunction(info.vram)
+            else:
+                contextSym = context.globalSegment.addSymbol(info.vram)
+                if info.type is not None:
+                    contextSym.setTypeIfUnset(info.type)
+            if contextSym.name is None:
+                contextSym.name = info.name
+            if info.size is not None:
+                contextSym.setSizeIfUnset(info.size)
+            contextSym.isUserDeclared = True
+            count += 1
+        return count
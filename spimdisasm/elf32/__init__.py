@@ -11,5 +11,6 @@ from .Elf32SectionHeaders import Elf32SectionHeaders, Elf32SectionHeaderEntry
 from .Elf32StringTable import Elf32StringTable
 from .Elf32Syms import Elf32Syms, Elf32SymEntry
 from .Elf32Rels import Elf32Rels, Elf32RelEntry
+from .Elf32Mdebug import Elf32Mdebug, Elf32MdebugHeader, Elf32MdebugFileDescriptor, Elf32MdebugSymEntry, Elf32MdebugSymbolInfo
 
 from .Elf32File import Elf32File
@@ -5,8 +5,9 @@
 
 from __future__ import annotations
 
+import dataclasses
 import os
-from typing import TextIO
+from typing import BinaryIO, TextIO
 
 from .. import common
 
@@ -14,7 +15,7 @@ from . import sections
 from . import symbols
 
 
-def createSectionFromSplitEntry(splitEntry: common.FileSplitEntry, array_of_bytes: bytearray, outputPath: str, context: common.Context) -> sections.SectionBase:
+def createSectionFromSplitEntry(splitEntry: common.FileSplitEntry, array_of_bytes: common.Utils.BytesBuffer, outputPath: str, context: common.Context) -> sections.SectionBase:
     head, tail = os.path.split(outputPath)
 
     offsetStart = splitEntry.offset
@@ -48,6 +49,30 @@ def createSectionFromSplitEntry(splitEntry: common.FileSplitEntry, array_of_byte
 
     return f
 
+def createSectionFromReader(context: common.Context, sectionType: common.FileSectionType, reader: BinaryIO, vromStart: int, vromEnd: int, vram: int, filename: str, segmentVromStart: int=0, overlayCategory: str|None=None) -> sections.SectionBase:
+    """Creates a text, data or rodata section reading only the [`vromStart`, `vromEnd`) range of `reader`, so the rest of the input is never loaded.
+
+    `reader` must be seekable. Raises a `RuntimeError` if the range can't be fully read"""
+    reader.seek(vromStart)
+    sectionBytes = bytearray(reader.read(vromEnd - vromStart))
+    if len(sectionBytes) != vromEnd - vromStart:
+        raise RuntimeError(f"Unable to read the range [0x{vromStart:X}, 0x{vromEnd:X}) of '{filename}', only 0x{len(sectionBytes):X} bytes were available")
+
+    f: sections.SectionBase
+    if sectionType == common.FileSectionType.Text:
+        f = sections.SectionText(context, 0, len(sectionBytes), vram, filename, sectionBytes, segmentVromStart, overlayCategory)
+    elif sectionType == common.FileSectionType.Data:
+        f = sections.SectionData(context, 0, len(sectionBytes), vram, filename, sectionBytes, segmentVromStart, overlayCategory)
+    elif sectionType == common.FileSectionType.Rodata:
+        f = sections.SectionRodata(context, 0, len(sectionBytes), vram, filename, sectionBytes, segmentVromStart, overlayCategory)
+    else:
+        raise RuntimeError(f"Sections of type '{sectionType.toStr()}' can't be read from a reader")
+
+    # The words were read relative to the buffer, so place the section on its actual rom range
+    f.vromStart = vromStart
+    f.vromEnd = vromEnd
+    return f
+
 def writeSection(path: str, fileSection: sections.SectionBase):
     head, tail = os.path.split(path)
 
@@ -60,7 +85,113 @@ def writeSection(path: str, fileSection: sections.SectionBase):
     return path
 
 
-def getRdataAndLateRodataForFunction(func: symbols.SymbolFunction, rodataFileList: list[sections.SectionRodata]):
+def getMacroIncContents() -> str:
+    "Generates the definitions of the label macros used by the generated assembly, according to the current `GlobalConfig`"
+    macros: dict[str, list[str]] = dict()
+
+    if common.GlobalConfig.ASM_TEXT_LABEL == common.GlobalConfig.ASM_DATA_LABEL:
+        macros[common.GlobalConfig.ASM_TEXT_LABEL] = [".global \\label", "\\label:"]
+    else:
+        macros[common.GlobalConfig.ASM_TEXT_LABEL] = [".global \\label", ".type \\label, @function", "\\label:"]
+        macros[common.GlobalConfig.ASM_DATA_LABEL] = [".global \\label", ".type \\label, @object", "\\label:"]
+
+    # Labels starting with a dot are assembler directives, so they don't need a macro
+    if common.GlobalConfig.ASM_TEXT_ENT_LABEL and not common.GlobalConfig.ASM_TEXT_ENT_LABEL.startswith("."):
+        macros[common.GlobalConfig.ASM_TEXT_ENT_LABEL] = [".ent \\label"]
+    if common.GlobalConfig.ASM_TEXT_END_LABEL and not common.GlobalConfig.ASM_TEXT_END_LABEL.startswith("."):
+        macros[common.GlobalConfig.ASM_TEXT_END_LABEL] = [".end \\label"]
+
+    output = ""
+    for macroName, body in macros.items():
+        if macroName.startswith("."):
+            continue
+        output += f".macro {macroName} label" + common.GlobalConfig.LINE_ENDS
+        for line in body:
+            output += f"    {line}" + common.GlobalConfig.LINE_ENDS
+        output += ".endm" + common.GlobalConfig.LINE_ENDS
+        output += common.GlobalConfig.LINE_ENDS
+    return output
+
+def writeMacroInc(path: str):
+    head, tail = os.path.split(path)
+
+    # Create directories
+    if head != "":
+        os.makedirs(head, exist_ok=True)
+
+    with open(path, "w") as f:
+        f.write(getMacroIncContents())
+
+    return path
+
+
+class RodataPairingError(RuntimeError):
+    "The rodata symbol is referenced by functions of different files, so it can't be migrated to only one of them"
+
+    def __init__(self, rodataSym: common.ContextSymbol, candidates: list[common.ContextSymbol]):
+        self.rodataSym = rodataSym
+        self.candidates = candidates
+        candidatesNames = ", ".join(f"'{func.getName()}' (0x{func.vram:08X})" for func in candidates)
+        super().__init__(f"The rodata symbol '{rodataSym.getName()}' (0x{rodataSym.vram:08X}) is referenced by functions of different files: {candidatesNames}")
+
+
+def getRodataOwners(funcs: list[symbols.SymbolFunction], rodataFileList: list[sections.SectionRodata]) -> dict[int, list[symbols.SymbolFunction]]:
+    """Maps the vram of every rodata symbol referenced by any of the functions of `funcs` to the functions referencing it.
+
+    Rodata symbols are sorted by vram, and so are the functions referencing each of them, so the result doesn't depend on the order of the inputs"""
+    owners: dict[int, list[symbols.SymbolFunction]] = dict()
+    for rodataSection in rodataFileList:
+        for vram in sorted(rodataSection.symbolsVRams):
+            candidates = [func for func in funcs if vram in func.instrAnalyzer.referencedVrams]
+            if len(candidates) > 0:
+                owners[vram] = sorted(candidates, key=lambda func: func.vram)
+    return dict(sorted(owners.items()))
+
+def getAmbiguousRodataPairings(funcs: list[symbols.SymbolFunction], rodataFileList: list[sections.SectionRodata]) -> list[RodataPairingError]:
+    "Returns an error for every rodata symbol referenced by functions of different files, sorted by the vram of the rodata symbol"
+    errors: list[RodataPairingError] = list()
+    for vram, candidates in getRodataOwners(funcs, rodataFileList).items():
+        parents = {id(func.parent) for func in candidates}
+        if len(parents) > 1:
+            rodataSym = candidates[0].getSymbol(vram, tryPlusOffset=False)
+            if rodataSym is None:
+                continue
+            errors.append(RodataPairingError(rodataSym, [func.contextSym for func in candidates]))
+    return errors
+
+def checkRodataPairing(funcs: list[symbols.SymbolFunction], rodataFileList: list[sections.SectionRodata]) -> None:
+    "Raises a `RodataPairingError` if a rodata symbol is referenced by functions of different files, so the rodata migration would be ambiguous"
+    errors = getAmbiguousRodataPairings(funcs, rodataFileList)
+    if len(errors) > 0:
+        raise errors[0]
+
+
+def getForcedRodataMigrations(rodataFileList: list[sections.SectionRodata]) -> dict[int, list[symbols.SymbolBase]]:
+    """Maps the vram of every function to the rodata symbols explicitly migrated to it with `ContextSymbol.migrateToFunction`, sorted by vram.
+
+    Raises a `RuntimeError` if any of those symbols must be migrated to an address which isn't a function"""
+    forcedMigrations: dict[int, list[symbols.SymbolBase]] = dict()
+    for rodataSection in rodataFileList:
+        for rodataSym in rodataSection.symbolList:
+            targetVram = rodataSym.contextSym.migrateToFunction
+            if targetVram is None:
+                continue
+            targetSym = rodataSym.getSymbol(targetVram, tryPlusOffset=False)
+            if targetSym is None or targetSym.type != common.SymbolSpecialType.function:
+                raise RuntimeError(f"The rodata symbol '{rodataSym.getName()}' must be migrated to 0x{targetVram:08X}, but there's no function at that address")
+            forcedMigrations.setdefault(targetVram, []).append(rodataSym)
+
+    for migratedList in forcedMigrations.values():
+        migratedList.sort(key=lambda x: x.vram)
+    return forcedMigrations
+
+def getRdataAndLateRodataForFunction(func: symbols.SymbolFunction, rodataFileList: list[sections.SectionRodata], forcedMigrations: dict[int, list[symbols.SymbolBase]]|None=None):
+    """Returns the rdata and late_rodata symbols migrated to `func`, and the size of the late_rodata in words.
+
+    `forcedMigrations` should be the result of `getForcedRodataMigrations` for the same `rodataFileList`, so it can be reused for every function. It is computed if not passed"""
+    if forcedMigrations is None:
+        forcedMigrations = getForcedRodataMigrations(rodataFileList)
+
     rdataList: list[symbols.SymbolBase] = []
     lateRodataList: list[symbols.SymbolBase] = []
     lateRodataSize = 0
@@ -79,8 +210,13 @@ def getRdataAndLateRodataForFunction(func: symbols.SymbolFunction, rodataFileLis
             if rodataSym.vram not in intersection:
                 continue
 
-            # We only care for rodata that's used once
-            if rodataSym.contextSym.referenceCounter != 1:
+            if rodataSym.contextSym.migrateToFunction is not None:
+                # Explicitly migrated, handled below
+                continue
+
+            # We only care for rodata that's used once, or strings used by few enough functions
+            assert isinstance(rodataSym, symbols.SymbolRodata)
+            if not rodataSym.isMigratable():
                 break
 
             # A const variable should not be placed with a function
@@ -91,12 +227,58 @@ def getRdataAndLateRodataForFunction(func: symbols.SymbolFunction, rodataFileLis
                 lateRodataList.append(rodataSym)
                 lateRodataSize += rodataSym.sizew
             else:
+                if len(lateRodataList) > 0:
+                    # IDO places the late_rodata after every other rodata of the file, so this pairing may not match the original layout
+                    common.Utils.epprintQuietless(f"Warning: The rodata symbol '{rodataSym.getName()}' is placed after the late_rodata symbol '{lateRodataList[-1].getName()}' of the function '{func.getName()}'. The rodata may not belong to an IDO-compiled file, try changing the selected compiler.")
                 rdataList.append(rodataSym)
 
+    # Symbols explicitly migrated to this function, regardless of the heuristics above
+    forcedList = forcedMigrations.get(func.vram, [])
+    for rodataSym in forcedList:
+        if rodataSym.contextSym.isLateRodata():
+            lateRodataList.append(rodataSym)
+            lateRodataSize += rodataSym.sizew
+        else:
+            rdataList.append(rodataSym)
+
+    if len(forcedList) > 0:
+        rdataList.sort(key=lambda x: x.vram)
+        lateRodataList.sort(key=lambda x: x.vram)
+
     return rdataList, lateRodataList, lateRodataSize
 
-def writeSplittedFunctionToFile(f: TextIO, func: symbols.SymbolFunction, rodataFileList: list[sections.SectionRodata]):
-    rdataList, lateRodataList, lateRodataSize = getRdataAndLateRodataForFunction(func, rodataFileList)
+
+@dataclasses.dataclass
+class FunctionRodataPairing:
+    "Rodata symbols migrated to a function, in the same order they are emitted by `writeSplittedFunctionToFile`"
+
+    functionVram: int
+    rdataVrams: list[int] = dataclasses.field(default_factory=list)
+    lateRodataVrams: list[int] = dataclasses.field(default_factory=list)
+    lateRodataSize: int = 0
+    "Size of the late_rodata, in words"
+
+    @property
+    def migratedVrams(self) -> list[int]:
+        return self.rdataVrams + self.lateRodataVrams
+
+def getFunctionsRodataPairing(funcs: list[symbols.SymbolFunction], rodataFileList: list[sections.SectionRodata]) -> list[FunctionRodataPairing]:
+    """Returns the result of migrating the rodata to each function of `funcs`, in the same order as `funcs`.
+
+    Functions without any migrated rodata are included, with empty lists.
+    Raises a `RodataPairingError` if a rodata symbol is referenced by functions of different files"""
+    checkRodataPairing(funcs, rodataFileList)
+    forcedMigrations = getForcedRodataMigrations(rodataFileList)
+
+    result: list[FunctionRodataPairing] = list()
+    for func in funcs:
+        rdataList, lateRodataList, lateRodataSize = getRdataAndLateRodataForFunction(func, rodataFileList, forcedMigrations)
+        pairing = FunctionRodataPairing(func.vram, [sym.vram for sym in rdataList], [sym.vram for sym in lateRodataList], lateRodataSize)
+        result.append(pairing)
+    return result
+
+def writeSplittedFunctionToFile(f: TextIO, func: symbols.SymbolFunction, rodataFileList: list[sections.SectionRodata], forcedMigrations: dict[int, list[symbols.SymbolBase]]|None=None):
+    rdataList, lateRodataList, lateRodataSize = getRdataAndLateRodataForFunction(func, rodataFileList, forcedMigrations)
 
     if len(rdataList) > 0:
         # Write the rdata
@@ -124,10 +306,10 @@ def writeSplittedFunctionToFile(f: TextIO, func: symbols.SymbolFunction, rodataF
     # Write the function
     f.write(func.disassemble())
 
-def writeSplitedFunction(path: str, func: symbols.SymbolFunction, rodataFileList: list[sections.SectionRodata]):
+def writeSplitedFunction(path: str, func: symbols.SymbolFunction, rodataFileList: list[sections.SectionRodata], forcedMigrations: dict[int, list[symbols.SymbolBase]]|None=None):
     os.makedirs(path, exist_ok=True)
     with open(os.path.join(path, func.getName()) + ".s", "w") as f:
-        writeSplittedFunctionToFile(f, func, rodataFileList)
+        writeSplittedFunctionToFile(f, func, rodataFileList, forcedMigrations)
 
 
 def writeOtherRodata(path: str, rodataFileList: list[sections.SectionRodata]):
@@ -8,20 +8,25 @@ from __future__ import annotations
 import argparse
 import rabbitizer
 
-from ..common import Utils
+from ..common import Utils, GlobalConfig, Abi
 
 
 class InstructionConfig:
+    PSEUDO_NOP: bool = True
+    """Use the `nop` pseudo-instruction instead of `sll $zero, $zero, 0`. Disabling rabbitizer's pseudo-instructions disables this one too"""
+
     @staticmethod
     def addParametersToArgParse(parser: argparse.ArgumentParser):
         registerNames = parser.add_argument_group("MIPS register names options")
 
+        registerNames.add_argument("--abi", help=f"ABI the code was compiled for. Sets the default names of the GPR and FPR registers, and the ABIs with 64-bit registers also recognize `daddiu $sp` as a stack frame adjustment. Defaults to {GlobalConfig.ABI.value}", choices=["o32", "n32", "n64"])
         registerNames.add_argument("--named-registers", help=f"(Dis)allows named registers for every instruction. This flag takes precedence over similar flags in this category. Defaults to {rabbitizer.config.regNames_namedRegisters}", action=Utils.BooleanOptionalAction)
 
         abi_choices = ["numeric", "32", "o32", "n32", "n64"]
         registerNames.add_argument("--Mgpr-names", help=f"Use GPR names according to the specified ABI. Defaults to {rabbitizer.config.regNames_gprAbiNames.name.lower()}", choices=abi_choices)
         registerNames.add_argument("--Mfpr-names", help=f"Use FPR names according to the specified ABI. Defaults to {rabbitizer.config.regNames_fprAbiNames.name.lower()}", choices=abi_choices)
         registerNames.add_argument("--Mreg-names", help=f"Use GPR and FPR names according to the specified ABI. This flag takes precedence over --Mgpr-names and --Mfpr-names", choices=abi_choices)
+        registerNames.add_argument("--register-naming", help=f"Use a single naming convention for the GPR, FPR and Coprocessor 0 registers. `numeric` also disables the named Coprocessor 0 registers. This flag takes precedence over every other flag in this category", choices=abi_choices)
 
         registerNames.add_argument("--use-fpccsr", help=f"Toggles using the FpcCsr alias for float register $31 when using the numeric ABI. Defaults to {rabbitizer.config.regNames_userFpcCsr}", action=Utils.BooleanOptionalAction)
 
@@ -31,7 +36,12 @@ class InstructionConfig:
 
         miscOpts = parser.add_argument_group("MIPS misc instructions options")
 
-        miscOpts.add_argument("--pseudo-instr", help=f"Toggles producing pseudo instructions. Defaults to {rabbitizer.config.pseudos_enablePseudos}", action=Utils.BooleanOptionalAction)
+        miscOpts.add_argument("--pseudo-instr", help=f"Toggles producing pseudo instructions. This flag takes precedence over the per pseudo-instruction flags. Defaults to {rabbitizer.config.pseudos_enablePseudos}", action=Utils.BooleanOptionalAction)
+        miscOpts.add_argument("--pseudo-move", help=f"Toggles producing the move pseudo instruction (`or $a, $b, $zero`). Defaults to {rabbitizer.config.pseudos_pseudoMove}", action=Utils.BooleanOptionalAction)
+        miscOpts.add_argument("--pseudo-beqz", help=f"Toggles producing the beqz pseudo instruction (`beq $a, $zero, ...`). Defaults to {rabbitizer.config.pseudos_pseudoBeqz}", action=Utils.BooleanOptionalAction)
+        miscOpts.add_argument("--pseudo-bnez", help=f"Toggles producing the bnez pseudo instruction (`bne $a, $zero, ...`). Defaults to {rabbitizer.config.pseudos_pseudoBnez}", action=Utils.BooleanOptionalAction)
+        miscOpts.add_argument("--pseudo-b", help=f"Toggles producing the b pseudo instruction (`beq $zero, $zero, ...`). Defaults to {rabbitizer.config.pseudos_pseudoB}", action=Utils.BooleanOptionalAction)
+        miscOpts.add_argument("--pseudo-nop", help=f"Toggles producing the nop pseudo instruction (`sll $zero, $zero, 0`). Defaults to {InstructionConfig.PSEUDO_NOP}", action=Utils.BooleanOptionalAction)
 
         miscOpts.add_argument("--j-branch", help=f"Treat J instructions as unconditional branches. {rabbitizer.config.toolchainTweaks_treatJAsUnconditionalBranch}", action=Utils.BooleanOptionalAction)
 
@@ -42,8 +52,30 @@ class InstructionConfig:
         miscOpts.add_argument("--unk-instr-comment", help=f"Disables the extra comment produced after unknown instructions. Defaults to {rabbitizer.config.misc_unknownInstrComment}", action=Utils.BooleanOptionalAction)
 
 
+    @staticmethod
+    def setRegisterNaming(abiName: str):
+        "Configures the names of the GPR, FPR and Coprocessor 0 registers according to a single naming convention, so conventions can't be mixed"
+        abi = rabbitizer.Abi.fromStr(abiName)
+        isNumeric = abiName == "numeric"
+
+        rabbitizer.config.regNames_namedRegisters = True
+        rabbitizer.config.regNames_gprAbiNames = abi
+        rabbitizer.config.regNames_fprAbiNames = abi
+        rabbitizer.config.regNames_vr4300Cop0NamedRegisters = not isNumeric
+        rabbitizer.config.regNames_vr4300RspCop0NamedRegisters = not isNumeric
+
+    @staticmethod
+    def setAbi(abi: Abi):
+        "Sets `GlobalConfig.ABI` and uses the register names of that ABI for the GPR and FPR registers"
+        GlobalConfig.ABI = abi
+        rabbitizer.config.regNames_gprAbiNames = rabbitizer.Abi.fromStr(abi.value)
+        rabbitizer.config.regNames_fprAbiNames = rabbitizer.Abi.fromStr(abi.value)
+
     @staticmethod
     def parseArgs(args: argparse.Namespace):
+        if args.abi:
+            InstructionConfig.setAbi(Abi.fromStr(args.abi))
+
         if args.named_registers is not None:
             rabbitizer.config.regNames_namedRegisters = args.named_registers
 
@@ -63,8 +95,21 @@ class InstructionConfig:
         if args.rsp_cop0_named_registers is not None:
             rabbitizer.config.regNames_vr4300RspCop0NamedRegisters = args.rsp_cop0_named_registers
 
+        if args.register_naming:
+            InstructionConfig.setRegisterNaming(args.register_naming)
+
         if args.pseudo_instr is not None:
             rabbitizer.config.pseudos_enablePseudos = args.pseudo_instr
+        if args.pseudo_move is not None:
+            rabbitizer.config.pseudos_pseudoMove = args.pseudo_move
+        if args.pseudo_beqz is not None:
+            rabbitizer.config.pseudos_pseudoBeqz = args.pseudo_beqz
+        if args.pseudo_bnez is not None:
+            rabbitizer.config.pseudos_pseudoBnez = args.pseudo_bnez
+        if args.pseudo_b is not None:
+            rabbitizer.config.pseudos_pseudoB = args.pseudo_b
+        if args.pseudo_nop is not None:
+            InstructionConfig.PSEUDO_NOP = args.pseudo_nop
 
         if args.j_branch is not None:
             rabbitizer.config.toolchainTweaks_treatJAsUnconditionalBranch = args.j_branch
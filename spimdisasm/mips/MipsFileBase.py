@@ -5,17 +5,25 @@
 
 from __future__ import annotations
 
+import io
 import sys
-from typing import TextIO
+from typing import Generator, TextIO
 
 from .. import common
 
 from . import symbols
+from .MipsRelocInfo import RelocInfo
 
 
 class FileBase(common.ElementBase):
-    def __init__(self, context: common.Context, vromStart: int, vromEnd: int, vram: int, filename: str, array_of_bytes: bytearray, sectionType: common.FileSectionType, segmentVromStart: int, overlayCategory: str|None):
-        super().__init__(context, vromStart, vromEnd, 0, vram, filename, common.Utils.bytesToBEWords(array_of_bytes, vromStart, vromEnd), sectionType, segmentVromStart, overlayCategory)
+    def __init__(self, context: common.Context, vromStart: int, vromEnd: int, vram: int, filename: str, array_of_bytes: common.Utils.BytesBuffer, sectionType: common.FileSectionType, segmentVromStart: int, overlayCategory: str|None):
+        super().__init__(context, vromStart, vromEnd, 0, vram, filename, [], sectionType, segmentVromStart, overlayCategory)
+        # The words can't be read until the segment (and its endianness) is known
+        self.words = common.Utils.bytesToBEWords(array_of_bytes, vromStart, vromEnd, endian=self.getEndian())
+        # Same range rule as `bytesToBEWords`: an end of 0 means the end of the input
+        rawBytesEnd = vromEnd if vromEnd > 0 else len(array_of_bytes)
+        self.rawBytes: bytes = bytes(array_of_bytes[vromStart:rawBytesEnd])
+        "Exact input slice `words` was decoded from. Kept untouched, unlike `words`, which may be modified by the analysis"
 
         self.symbolList: list[symbols.SymbolBase] = []
 
@@ -43,7 +51,8 @@ class FileBase(common.ElementBase):
         output += "# assembler directives" + common.GlobalConfig.LINE_ENDS
         output += ".set noat      # allow manual use of $at" + common.GlobalConfig.LINE_ENDS
         output += ".set noreorder # don't insert nops after branches" + common.GlobalConfig.LINE_ENDS
-        output += ".set gp=64     # allow use of 64-bit general purpose registers" + common.GlobalConfig.LINE_ENDS
+        if common.GlobalConfig.ASM_GP_64:
+            output += ".set gp=64     # allow use of 64-bit general purpose registers" + common.GlobalConfig.LINE_ENDS
         output += common.GlobalConfig.LINE_ENDS
         output += f".section {self.sectionType.toSectionName()}" + common.GlobalConfig.LINE_ENDS
         output += common.GlobalConfig.LINE_ENDS
@@ -51,6 +60,12 @@ class FileBase(common.ElementBase):
 
         return output
 
+    def getRawBytes(self) -> bytes:
+        """Returns the exact input bytes of this section, as they were before any analysis or pointer removal.
+
+        Concatenating the raw bytes of consecutive sections reproduces the original rom region"""
+        return self.rawBytes
+
     def getHash(self) -> str:
         buffer = bytearray(4*len(self.words))
         common.Utils.beWordsToBytes(self.words, buffer)
@@ -64,12 +79,47 @@ class FileBase(common.ElementBase):
             return
 
         currentVram = self.getVramOffset(0)
-        vrom = self.getVromOffset(0)
+        vrom: int|None = self.getVromOffset(0)
+        if self.sectionType == common.FileSectionType.Bss:
+            # bss doesn't take space in the rom, so its vrom can't be used to know which segment owns it
+            vrom = None
         contextSym = self.getSymbol(currentVram, tryPlusOffset=False)
         if contextSym is None:
             contextSym = self.addSymbol(currentVram, sectionType=self.sectionType, isAutogenerated=True, symbolVrom=vrom)
 
 
+    def getSymbolsAffectedByVram(self, vramStart: int, vramEnd: int) -> list[symbols.SymbolBase]:
+        "Returns the symbols of this file which contain or reference the [`vramStart`, `vramEnd`) range"
+        affected: list[symbols.SymbolBase] = []
+        for sym in self.symbolList:
+            if sym.vram < vramEnd and vramStart < sym.vramEnd:
+                affected.append(sym)
+            elif sym.referencesVramRange(vramStart, vramEnd):
+                affected.append(sym)
+        return affected
+
+    def addUserSymbol(self, vram: int, name: str, size: int|None=None, symbolType: common.SymbolSpecialType|str|None=None) -> list[symbols.SymbolBase]:
+        """Adds or renames a user declared symbol after this file has been analyzed.
+
+        Symbol names are resolved when disassembling, so instead of analyzing the whole file again only the symbols returned by this
+        function need to be disassembled again. Other files which may reference the new symbol can be checked with `getSymbolsAffectedByVram`."""
+        contextSym = self.getSymbol(vram, tryPlusOffset=False)
+        if contextSym is None:
+            sectionType = common.FileSectionType.Unknown
+            if self.vram <= vram < self.vramEnd:
+                sectionType = self.sectionType
+            contextSym = self.addSymbol(vram, sectionType=sectionType)
+        contextSym.name = name
+        contextSym.isUserDeclared = True
+        contextSym.isAutogenerated = False
+        if size is not None:
+            contextSym.size = size
+        if symbolType is not None:
+            contextSym.type = symbolType
+
+        return self.getSymbolsAffectedByVram(vram, vram + contextSym.getSize())
+
+
     def printNewFileBoundaries(self):
         if not common.GlobalConfig.PRINT_NEW_FILE_BOUNDARIES:
             return
@@ -152,18 +202,62 @@ class FileBase(common.ElementBase):
         return False
 
 
+    def getRelocations(self) -> list[RelocInfo]:
+        "Returns the relocations needed to relink this section, sorted by offset"
+        relocs: list[RelocInfo] = list()
+        for sym in self.symbolList:
+            relocs += sym.getRelocations()
+        relocs.sort(key=lambda reloc: reloc.offset)
+        return relocs
+
+
+    def writeDisassembly(self, f: TextIO):
+        """Writes the disassembly of each symbol as soon as it is generated, instead of building the whole section in memory first"""
+        for i, sym in enumerate(self.symbolList):
+            f.write(sym.disassemble())
+            if i + 1 < len(self.symbolList):
+                f.write(common.GlobalConfig.LINE_ENDS)
+
     def disassemble(self) -> str:
-        output = ""
+        output = io.StringIO()
+        self.writeDisassembly(output)
+        return output.getvalue()
+
+    def iterDisassemblyByFile(self, splitPoints: list[tuple[int, str]]) -> Generator[tuple[str, str], None, None]:
+        """Generator which splits the disassembly of this section at each `(vram, fileName)` split point, yielding `(fileName, disassembly)` chunks.
+
+        Symbols placed before the first split point are yielded with the name of this section. Symbols are never split, so a split point
+        placed in the middle of a symbol starts at the next symbol. Concatenating every chunk produces the same output as `disassemble`"""
+        sortedSplits = sorted(splitPoints)
+        splitIndex = -1
+        currentName = self.name
+        currentChunk = ""
+
         for i, sym in enumerate(self.symbolList):
-            output += sym.disassemble()
+            nextName = currentName
+            while splitIndex + 1 < len(sortedSplits) and sortedSplits[splitIndex + 1][0] <= sym.vram:
+                splitIndex += 1
+                splitVram, nextName = sortedSplits[splitIndex]
+                if splitVram != sym.vram:
+                    common.Utils.epprintQuietless(f"Warning: Split point 0x{splitVram:08X} ('{nextName}') doesn't match the start of any symbol. Starting it at '{sym.getName()}' instead")
+
+            if nextName != currentName:
+                if currentChunk != "":
+                    yield currentName, currentChunk
+                currentName = nextName
+                currentChunk = ""
+
+            currentChunk += sym.disassemble()
             if i + 1 < len(self.symbolList):
-                output += common.GlobalConfig.LINE_ENDS
-        return output
+                currentChunk += common.GlobalConfig.LINE_ENDS
+
+        if currentChunk != "":
+            yield currentName, currentChunk
 
     def disassembleToFile(self, f: TextIO):
         f.write(self.getAsmPrelude())
         f.write(common.GlobalConfig.LINE_ENDS)
-        f.write(self.disassemble())
+        self.writeDisassembly(f)
 
 
     def saveToFile(self, filepath: str):
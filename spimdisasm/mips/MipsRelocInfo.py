@@ -0,0 +1,54 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import dataclasses
+
+from .MipsRelocTypes import RelocTypes
+
+
+@dataclasses.dataclass
+class RelocInfo:
+    offset: int
+    "Offset of the relocated word, relative to the start of its section"
+
+    relocType: RelocTypes
+
+    symbol: str
+    "Name of the symbol this relocation references"
+
+    addend: int = 0
+
+
+    def computeValue(self, symbolVram: int, gpValue: int|None=None) -> int:
+        """Returns the bits this relocation writes to the relocated word, following the MIPS ELF ABI, once the symbol is placed at `symbolVram`.
+
+        gp relative relocations (`S + A - GP`) require the `gpValue` of the linked executable"""
+        value = symbolVram + self.addend
+        if self.relocType.isGpRelative():
+            if gpValue is None:
+                raise RuntimeError(f"Relocation {self} is relative to gp, but no gp value was given")
+            value -= gpValue
+
+        if self.relocType == RelocTypes.R_MIPS_32 or self.relocType == RelocTypes.R_MIPS_GPREL32:
+            return value & 0xFFFFFFFF
+        if self.relocType == RelocTypes.R_MIPS_26:
+            return (value >> 2) & 0x3FFFFFF
+        if self.relocType == RelocTypes.R_MIPS_HI16:
+            # Compensates the sign extension of the paired lo16
+            return ((value + 0x8000) >> 16) & 0xFFFF
+        if self.relocType in {RelocTypes.R_MIPS_LO16, RelocTypes.R_MIPS_GPREL16, RelocTypes.R_MIPS_LITERAL}:
+            return value & 0xFFFF
+        raise RuntimeError(f"Can't compute the value of relocation {self}")
+
+
+    def __str__(self) -> str:
+        addendStr = ""
+        if self.addend > 0:
+            addendStr = f" + 0x{self.addend:X}"
+        elif self.addend < 0:
+            addendStr = f" - 0x{-self.addend:X}"
+        return f"0x{self.offset:06X} {self.relocType.name} {self.symbol}{addendStr}"
@@ -15,6 +15,9 @@ class RelocTypes(enum.Enum):
     R_MIPS_26    = 4
     R_MIPS_HI16  = 5
     R_MIPS_LO16  = 6
+    R_MIPS_GPREL16 = 7
+    R_MIPS_LITERAL = 8
+    R_MIPS_GPREL32 = 12
 
 
     @staticmethod
@@ -27,4 +30,14 @@ class RelocTypes(enum.Enum):
             return RelocTypes.R_MIPS_HI16
         if value == 6:
             return RelocTypes.R_MIPS_LO16
+        if value == 7:
+            return RelocTypes.R_MIPS_GPREL16
+        if value == 8:
+            return RelocTypes.R_MIPS_LITERAL
+        if value == 12:
+            return RelocTypes.R_MIPS_GPREL32
         return RelocTypes.INVALID
+
+    def isGpRelative(self) -> bool:
+        "Relocations of small data, which are computed relative to the gp value of the linked executable"
+        return self in {RelocTypes.R_MIPS_GPREL16, RelocTypes.R_MIPS_LITERAL, RelocTypes.R_MIPS_GPREL32}
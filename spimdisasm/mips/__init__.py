@@ -14,3 +14,4 @@ from .InstructionConfig import InstructionConfig
 from .MipsFileBase import FileBase, createEmptyFile
 from .MipsFileSplits import FileSplits
 from .MipsRelocTypes import RelocTypes
+from .MipsRelocInfo import RelocInfo
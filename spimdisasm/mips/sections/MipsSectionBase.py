@@ -9,7 +9,31 @@ from ... import common
 
 from ..MipsFileBase import FileBase
 
+
+class SectionAnalysisError(RuntimeError):
+    "The analysis of the section produced too many autogenerated symbols, see `GlobalConfig.MAX_AUTOGENERATED_SYMBOLS_PER_SECTION`"
+
+    def __init__(self, section: FileBase, symbolsCount: int, maxSymbols: int):
+        self.section = section
+        self.symbolsCount = symbolsCount
+        self.maxSymbols = maxSymbols
+        super().__init__(f"The analysis of '{section.name}' (0x{section.vromStart:X}-0x{section.vromEnd:X}) produced {symbolsCount} autogenerated symbols, more than the limit of {maxSymbols}. This section may be data analyzed as code, or have wrong boundaries")
+
+
 class SectionBase(FileBase):
+    def checkAutogeneratedSymbolsLimit(self) -> None:
+        "Raises a `SectionAnalysisError` if this section contains more autogenerated symbols than `GlobalConfig.MAX_AUTOGENERATED_SYMBOLS_PER_SECTION`"
+        maxSymbols = common.GlobalConfig.MAX_AUTOGENERATED_SYMBOLS_PER_SECTION
+        if maxSymbols <= 0:
+            return
+
+        symbolsCount = 0
+        for _, contextSym in self.getSymbolsRange(self.vram, self.vramEnd):
+            if contextSym.isAutogenerated:
+                symbolsCount += 1
+        if symbolsCount > maxSymbols:
+            raise SectionAnalysisError(self, symbolsCount, maxSymbols)
+
     def blankOutDifferences(self, other: FileBase) -> bool:
         if not common.GlobalConfig.REMOVE_POINTERS:
             return False
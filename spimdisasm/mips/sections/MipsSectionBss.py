@@ -35,7 +35,18 @@ class SectionBss(SectionBase):
         self.bssVramStart = vram
         self.bssVramEnd = vram + self.bssTotalSize
 
+    def _claimBssRange(self):
+        # The bss of an overlay belongs to the overlay's segment, even if it lies past the vram range the segment was declared with.
+        # Otherwise its symbols would end up in the global or unknown segments, mixing them with the bss of other segments using the same addresses
+        if self.overlayCategory is None:
+            return
+        segment = self.getSegment()
+        if segment.overlayCategory == self.overlayCategory:
+            segment.addBssVramRange(self.bssVramStart, self.bssVramEnd)
+
     def analyze(self):
+        self._claimBssRange()
+
         self.checkAndCreateFirstSymbol()
 
         # If something that could be a pointer found in data happens to be in the middle of this bss file's addresses space
@@ -44,7 +55,8 @@ class SectionBss(SectionBase):
             # Check if the symbol already exists, in case the user has provided size
             contextSym = self.getSymbol(ptr, tryPlusOffset=True)
             if contextSym is None:
-                self.addSymbol(ptr, sectionType=self.sectionType, isAutogenerated=True)
+                contextSym = self.addSymbol(ptr, sectionType=self.sectionType, isAutogenerated=True)
+                contextSym.setOriginIfUnset(common.SymbolOrigin.dataPointer)
 
 
         offsetSymbolsInSection = self.context.offsetSymbols[common.FileSectionType.Bss]
@@ -8,20 +8,140 @@ from __future__ import annotations
 from ... import common
 
 from .. import symbols
+from ..MipsRelocTypes import RelocTypes
 
-from . import SectionBase
+from . import SectionBase, SectionText
 
 
 class SectionData(SectionBase):
-    def __init__(self, context: common.Context, vromStart: int, vromEnd: int, vram: int, filename: str, array_of_bytes: bytearray, segmentVromStart: int, overlayCategory: str|None):
+    def __init__(self, context: common.Context, vromStart: int, vromEnd: int, vram: int, filename: str, array_of_bytes: common.Utils.BytesBuffer, segmentVromStart: int, overlayCategory: str|None):
         super().__init__(context, vromStart, vromEnd, vram, filename, array_of_bytes, common.FileSectionType.Data, segmentVromStart, overlayCategory)
 
+        self.isPointerArray: bool = False
+        "Interprets the whole section as an array of function pointers, like the `.ctors` and `.dtors` tables emitted by GCC"
+
+        self.aggressivePointerDetection: bool = common.GlobalConfig.DATA_AGGRESSIVE_POINTER_DETECTION
+        "Consider every word pointing to a known segment as a possible pointer, instead of only the ones pointing after this section"
+
+        self.nullPointers: bool = common.GlobalConfig.DATA_NULL_POINTERS
+        "Keep the zero words next to pointers in the same symbol as null pointers, instead of starting a new symbol on every change between pointers and zeros"
+
+        self.structStride: int|None = None
+        "If set, the section is interpreted as an array of structs of this size. See `setStructArray`"
+        self.structPointerOffsets: set[int] = set()
+        "Offsets inside each struct of the fields which are pointers. Every other field is emitted as a plain word"
+
+        self.typedRegions: common.IntervalMap[bool] = common.IntervalMap()
+        "Vram ranges declared as text (`True`) or as binary data (`False`). See `addTypedRegion`"
+
+
+    def setStructArray(self, stride: int, pointerOffsets: set[int]) -> None:
+        """Interprets the whole section as an array of structs of `stride` bytes, where only the fields at `pointerOffsets` (relative to the start of each struct) are pointers.
+
+        Those fields are symbolized on every struct of the array, and every other word is emitted as-is even if it looks like a pointer"""
+        if stride <= 0 or stride % 4 != 0:
+            raise RuntimeError(f"The stride of the struct array of '{self.name}' must be a positive multiple of 4, got 0x{stride:X}")
+        for pointerOffset in pointerOffsets:
+            if pointerOffset % 4 != 0 or not (0 <= pointerOffset < stride):
+                raise RuntimeError(f"The pointer offset 0x{pointerOffset:X} of the struct array of '{self.name}' must be word aligned and inside the stride (0x{stride:X})")
+        self.structStride = stride
+        self.structPointerOffsets = set(pointerOffsets)
+
+    def addTypedRegion(self, vramStart: int, vramEnd: int, isString: bool) -> None:
+        """Declares the [`vramStart`, `vramEnd`) range as text, which is always emitted as strings, or as binary data, which is always emitted as words.
+
+        Symbols are placed on both ends of the range, so its contents never leak into the surrounding data. User declared symbols keep their types.
+        Regions can't overlap each other. This must be called before `analyze`"""
+        if vramStart % 4 != 0 or vramEnd % 4 != 0:
+            raise RuntimeError(f"The region [0x{vramStart:08X}, 0x{vramEnd:08X}) of '{self.name}' must be word aligned")
+        if vramStart < self.vram or vramEnd > self.vramEnd:
+            raise RuntimeError(f"The region [0x{vramStart:08X}, 0x{vramEnd:08X}) is outside of the section '{self.name}'")
+        self.typedRegions.insertRange(vramStart, vramEnd, isString)
+
+    def _applyTypedRegions(self):
+        for vramStart, vramEnd, isString in self.typedRegions:
+            for boundary in (vramStart, vramEnd):
+                if boundary < self.vramEnd and self.getSymbol(boundary, tryPlusOffset=False) is None:
+                    contextSym = self.addSymbol(boundary, sectionType=self.sectionType, isAutogenerated=True, symbolVrom=self.getVromOffset(boundary - self.vram))
+                    contextSym.setOriginIfUnset(common.SymbolOrigin.analysis)
+
+            for _, contextSym in self.getSymbolsRange(vramStart, vramEnd):
+                if contextSym.isUserDeclared:
+                    continue
+                if isString:
+                    contextSym.type = "char"
+                elif contextSym.isString():
+                    contextSym.type = None
+                    contextSym.isMaybeString = False
+
+    def _isStructPointerField(self, localOffset: int) -> bool:
+        assert self.structStride is not None
+        return localOffset % self.structStride in self.structPointerOffsets
+
+    def _analyzeStructArray(self):
+        for i, w in enumerate(self.words):
+            if not self._isStructPointerField(i*4) or w == 0:
+                continue
+            if not self.context.shouldCreateReference(self.getVramOffset(i*4), w, RelocTypes.R_MIPS_32.name):
+                continue
+
+            # Only trust pointers to known segments, so stray data doesn't produce false references
+            if self.getSegmentForVram(w) is self.context.unknownSegment:
+                continue
+
+            if self.getSymbol(w, tryPlusOffset=False) is None and common.GlobalConfig.ADD_NEW_SYMBOLS:
+                contextSym = self.addSymbol(w, isAutogenerated=True)
+                contextSym.setOriginIfUnset(common.SymbolOrigin.dataPointer, self.getVromOffset(i*4))
+
+    def _analyzePointerArray(self):
+        for i, w in enumerate(self.words):
+            # Keep null terminators and the -1 sentinel as literals
+            if w == 0 or w == 0xFFFFFFFF:
+                continue
+            if not self.context.shouldCreateReference(self.getVramOffset(i*4), w, RelocTypes.R_MIPS_32.name):
+                continue
+
+            # Only trust pointers to known segments, so stray data doesn't produce false references
+            if self.getSegmentForVram(w) is self.context.unknownSegment:
+                continue
+
+            if self.getSymbol(w, tryPlusOffset=False) is None and common.GlobalConfig.ADD_NEW_SYMBOLS:
+                funcSym = self.addFunction(w, isAutogenerated=True)
+                funcSym.setOriginIfUnset(common.SymbolOrigin.jump, self.getVromOffset(i*4))
+
+    def _isCodeThunk(self, contextSym: common.ContextSymbol) -> bool:
+        "Functions declared by the user inside a data section, like small trampolines, are disassembled as instructions"
+        return contextSym.isUserDeclared and contextSym.type == common.SymbolSpecialType.function
+
+    def _addThunksEnds(self):
+        # A thunk with a known size ends there, so create a symbol for the data that follows it
+        for localOffset in range(0, self.sizew*4, 4):
+            contextSym = self.getSymbol(self.getVramOffset(localOffset), tryPlusOffset=False)
+            if contextSym is None or not self._isCodeThunk(contextSym) or contextSym.size is None:
+                continue
+            endOffset = localOffset + contextSym.size
+            if endOffset >= self.sizew*4 or endOffset % 4 != 0:
+                continue
+            endVram = self.getVramOffset(endOffset)
+            if self.getSymbol(endVram, tryPlusOffset=False) is None:
+                dataSym = self.addSymbol(endVram, sectionType=self.sectionType, isAutogenerated=True, symbolVrom=self.getVromOffset(endOffset))
+                dataSym.setOriginIfUnset(common.SymbolOrigin.analysis)
 
     def analyze(self):
         self.checkAndCreateFirstSymbol()
+        self._addThunksEnds()
+        self._applyTypedRegions()
+
+        if self.isPointerArray:
+            self._analyzePointerArray()
+        elif self.structStride is not None:
+            self._analyzeStructArray()
 
         symbolList: list[tuple[int, common.ContextSymbol]] = []
         localOffset = 0
+        currentSym: common.ContextSymbol|None = None
+        # Whether the previous word was a pointer (True), a zero (False) or neither (None)
+        previousWasPointer: bool|None = None
 
         for w in self.words:
             currentVram = self.getVramOffset(localOffset)
@@ -29,13 +149,50 @@ class SectionData(SectionBase):
             contextSym = self.getSymbol(currentVram, tryPlusOffset=False)
             if contextSym is not None:
                 symbolList.append((localOffset, contextSym))
-
-            if w >= self.vram and w > 0x80000000 and w < 0x84000000:
+                currentSym = contextSym
+                previousWasPointer = None
+
+            isPointer: bool|None = None
+            if currentSym is not None and (currentSym.isInteger() or self._isCodeThunk(currentSym)):
+                # Scalars which coincidentally look like an address, or instructions
+                pass
+            elif self.structStride is not None:
+                # Already handled by the struct array analysis
+                pass
+            elif self.typedRegions.get(currentVram):
+                # Text can't contain pointers
+                pass
+            elif not self.context.shouldCreateReference(currentVram, w, RelocTypes.R_MIPS_32.name):
+                pass
+            elif self.aggressivePointerDetection and not self.isPointerArray:
+                isPointer = False if w == 0 else None
+                if w != 0 and w % 4 == 0 and self.getSegmentForVram(w) is not self.context.unknownSegment:
+                    isPointer = True
+                    if self.getSymbol(w, tryPlusOffset=False) is None:
+                        if not common.GlobalConfig.DATA_REFERENCES_WITH_ADDENDS or self.getSymbolForDataReference(w, True) is None:
+                            self.addPointerInDataReference(w)
+            elif not self.isPointerArray and w >= self.vram and w > 0x80000000 and w < 0x84000000:
+                isPointer = True
                 if self.getSymbol(w, tryPlusOffset=False) is None:
-                    self.addPointerInDataReference(w)
+                    # Pointers which are going to be emitted with an addend don't need a symbol of their own
+                    if not common.GlobalConfig.DATA_REFERENCES_WITH_ADDENDS or self.getSymbolForDataReference(w, True) is None:
+                        self.addPointerInDataReference(w)
+            elif not self.isPointerArray and w == 0:
+                isPointer = False
+
+            if not self.nullPointers and isPointer is not None and previousWasPointer is not None and isPointer != previousWasPointer:
+                # Split the pointer array on the boundaries between pointers and zeros
+                if contextSym is None:
+                    contextSym = self.addSymbol(currentVram, sectionType=self.sectionType, isAutogenerated=True, symbolVrom=self.getVromOffset(localOffset))
+                    contextSym.setOriginIfUnset(common.SymbolOrigin.analysis)
+                    symbolList.append((localOffset, contextSym))
+                    currentSym = contextSym
+            previousWasPointer = isPointer
 
             localOffset += 4
 
+        self.checkAutogeneratedSymbolsLimit()
+
         for i, (offset, contextSym) in enumerate(symbolList):
             if i + 1 == len(symbolList):
                 words = self.words[offset//4:]
@@ -45,13 +202,30 @@ class SectionData(SectionBase):
 
             vrom = self.getVromOffset(offset)
             vromEnd = vrom + 4*len(words)
+
+            if self._isCodeThunk(contextSym):
+                instrsList = SectionText.wordListToInstructions(words, contextSym.vram)
+                func = symbols.SymbolFunction(self.context, vrom, vromEnd, offset + self.inFileOffset, contextSym.vram, instrsList, self.segmentVromStart, self.overlayCategory)
+                func.parent = self
+                func.setCommentOffset(self.commentOffset)
+                func.analyze()
+                self.symbolList.append(func)
+
+                self.symbolsVRams.add(contextSym.vram)
+                continue
+
             sym = symbols.SymbolData(self.context, vrom, vromEnd, offset + self.inFileOffset, contextSym.vram, words, self.segmentVromStart, self.overlayCategory)
+            if self.structStride is not None:
+                sym.nonPointerOffsets = {j*4 for j in range(len(words)) if not self._isStructPointerField(offset + j*4)}
+            if self.nullPointers:
+                # Null pointers are never references to symbols placed at address zero
+                sym.nonPointerOffsets |= {j*4 for j, word in enumerate(words) if word == 0}
             sym.parent = self
             sym.setCommentOffset(self.commentOffset)
             sym.analyze()
             self.symbolList.append(sym)
 
-            self.symbolsVRams.add(vram)
+            self.symbolsVRams.add(contextSym.vram)
 
 
     def removePointers(self) -> bool:
@@ -0,0 +1,95 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+# MIPS specific sections emitted on elf files, like `.reginfo` and `.MIPS.abiflags`
+
+from __future__ import annotations
+
+import sys
+from typing import TextIO
+
+from ... import common
+
+from . import SectionBase
+
+
+# Elf32_RegInfo
+reginfoFieldsComments = [
+    "ri_gprmask",
+    "ri_cprmask[0]",
+    "ri_cprmask[1]",
+    "ri_cprmask[2]",
+    "ri_cprmask[3]",
+    "ri_gp_value",
+]
+
+# Elf_Internal_ABIFlags_v0
+abiFlagsFieldsComments = [
+    "version (16 bits), isa_level (8 bits), isa_rev (8 bits)",
+    "gpr_size, cpr1_size, cpr2_size, fp_abi (8 bits each)",
+    "isa_ext",
+    "ases",
+    "flags1",
+    "flags2",
+]
+
+
+class SectionPassthrough(SectionBase):
+    """Keeps the raw contents of a section spimdisasm doesn't analyze, so it can be reassembled as-is.
+
+    Only `FileSectionType.Reginfo` and `FileSectionType.AbiFlags` are supported"""
+
+    def __init__(self, context: common.Context, vromStart: int, vromEnd: int, vram: int, filename: str, array_of_bytes: common.Utils.BytesBuffer, sectionType: common.FileSectionType, segmentVromStart: int, overlayCategory: str|None):
+        if sectionType not in {common.FileSectionType.Reginfo, common.FileSectionType.AbiFlags}:
+            raise RuntimeError(f"Section type '{sectionType.name}' can't be passed through")
+        if len(array_of_bytes) % 4 != 0:
+            raise RuntimeError(f"Size of the '{sectionType.toStr()}' section of '{filename}' is not a multiple of 4")
+
+        super().__init__(context, vromStart, vromEnd, vram, filename, array_of_bytes, sectionType, segmentVromStart, overlayCategory)
+
+
+    def getAlignment(self) -> int:
+        if self.sectionType == common.FileSectionType.AbiFlags:
+            return 8
+        return 4
+
+    def getFieldsComments(self) -> list[str]:
+        if self.sectionType == common.FileSectionType.AbiFlags:
+            return abiFlagsFieldsComments
+        return reginfoFieldsComments
+
+
+    def getAsmPrelude(self) -> str:
+        output = ""
+
+        # The assembler infers the elf section type from its name
+        output += f".section {self.sectionType.toSectionName()}" + common.GlobalConfig.LINE_ENDS
+        output += common.GlobalConfig.LINE_ENDS
+        output += f".balign {self.getAlignment()}" + common.GlobalConfig.LINE_ENDS
+
+        return output
+
+    def analyze(self):
+        # Nothing to analyze, the contents are emitted verbatim
+        pass
+
+    def writeDisassembly(self, f: TextIO):
+        fieldsComments = self.getFieldsComments()
+
+        for i, w in enumerate(self.words):
+            f.write(f".word 0x{w:08X}")
+            if i < len(fieldsComments):
+                f.write(f" # {fieldsComments[i]}")
+            f.write(common.GlobalConfig.LINE_ENDS)
+
+    def saveToFile(self, filepath: str):
+        if self.sizew == 0:
+            return
+
+        if filepath == "-":
+            self.disassembleToFile(sys.stdout)
+        else:
+            with open(filepath + self.sectionType.toStr() + ".s", "w") as f:
+                self.disassembleToFile(f)
@@ -13,12 +13,17 @@ from . import SectionBase
 
 
 class SectionRodata(SectionBase):
-    def __init__(self, context: common.Context, vromStart: int, vromEnd: int, vram: int, filename: str, array_of_bytes: bytearray, segmentVromStart: int, overlayCategory: str|None):
+    def __init__(self, context: common.Context, vromStart: int, vromEnd: int, vram: int, filename: str, array_of_bytes: common.Utils.BytesBuffer, segmentVromStart: int, overlayCategory: str|None):
         super().__init__(context, vromStart, vromEnd, vram, filename, array_of_bytes, common.FileSectionType.Rodata, segmentVromStart, overlayCategory)
 
         self.bytes: bytearray = bytearray(self.sizew*4)
         common.Utils.beWordsToBytes(self.words, self.bytes)
 
+        self.stringGuesserMinLength: int|None = None
+        "Overrides `GlobalConfig.STRING_GUESSER_MIN_LENGTH` for this section"
+        self.stringGuesserMinPrintableRatio: float|None = None
+        "Overrides `GlobalConfig.STRING_GUESSER_MIN_PRINTABLE_RATIO` for this section"
+
 
     def _stringGuesser(self, contextSym: common.ContextSymbol, localOffset: int) -> bool:
         if contextSym.isMaybeString or contextSym.isString():
@@ -34,10 +39,55 @@ class SectionRodata(SectionBase):
         if self.bytes[localOffset] == 0:
             return False
 
+        # Don't let the string run into the next symbol
+        stringBoundary = self.getNextSymbolLocalOffset(localOffset)
+        isUnterminated = False
         try:
-            common.Utils.decodeString(self.bytes, localOffset)
+            _, rawStringSize = common.Utils.decodeString(self.bytes[:stringBoundary], localOffset, contextSym.getStringEncoding())
         except (UnicodeDecodeError, RuntimeError):
             # String can't be decoded
+            if not common.GlobalConfig.STRING_GUESSER_UNTERMINATED or stringBoundary >= len(self.bytes):
+                return False
+            # Fixed-width strings may fill the whole space until the next symbol without a terminator
+            try:
+                _, rawStringSize = common.Utils.decodeString(self.bytes[:stringBoundary], localOffset, contextSym.getStringEncoding(), allowUnterminated=True)
+            except (UnicodeDecodeError, RuntimeError):
+                return False
+            isUnterminated = True
+
+        minLength = self.stringGuesserMinLength if self.stringGuesserMinLength is not None else common.GlobalConfig.STRING_GUESSER_MIN_LENGTH
+        if rawStringSize < minLength:
+            return False
+
+        minPrintableRatio = self.stringGuesserMinPrintableRatio if self.stringGuesserMinPrintableRatio is not None else common.GlobalConfig.STRING_GUESSER_MIN_PRINTABLE_RATIO
+        if minPrintableRatio > 0:
+            decoded = self.bytes[localOffset:localOffset+rawStringSize].decode(contextSym.getStringEncoding())
+            printableCount = sum(1 for char in decoded if char.isprintable() or char in "\t\n\r")
+            if printableCount < minPrintableRatio * len(decoded):
+                return False
+        contextSym.isUnterminatedString = isUnterminated
+        return True
+
+    def _doubleGuesser(self, contextSym: common.ContextSymbol, localOffset: int, symbolSize: int) -> bool:
+        if contextSym.isMaybeDouble or contextSym.isDouble():
+            return True
+
+        if not common.GlobalConfig.DOUBLE_GUESSER:
+            return False
+
+        if not contextSym.hasNoType() or contextSym.isMaybeString:
+            return False
+
+        # The symbols accessed by instructions already got their type from the analysis
+        if len(contextSym.accessTypes) > 0:
+            return False
+
+        if contextSym.vram % 8 != 0 or symbolSize != 8:
+            return False
+
+        exponent = (self.words[localOffset//4] >> 20) & 0x7FF
+        # Filter out zero, denormals, NaN and infinity
+        if exponent == 0 or exponent == 0x7FF:
             return False
         return True
 
@@ -69,6 +119,25 @@ class SectionRodata(SectionBase):
                     # print(relocSymbol.name, f"{w:X}")
             inFileOffset += 4
 
+    def _isGpRelJumpTableEntry(self, w: int, jumpTableTargetsRange: tuple[int, int]|None) -> bool:
+        "Checks if the first entry of a jump table is an offset relative to `GlobalConfig.GP_VALUE` instead of an address"
+        if ((w >> 24) & 0xFF) == 0x80:
+            return False
+        target = symbols.SymbolRodata.resolveGpRelEntry(w)
+        if target is None:
+            return False
+        if jumpTableTargetsRange is not None:
+            return jumpTableTargetsRange[0] <= target < jumpTableTargetsRange[1]
+        return ((target >> 24) & 0xFF) == 0x80
+
+    def _resolveJumpTableEntry(self, w: int, jumpTableSym: common.ContextSymbol|None) -> int:
+        if jumpTableSym is None or not jumpTableSym.isGpRelJumpTable:
+            return w
+        target = symbols.SymbolRodata.resolveGpRelEntry(w)
+        if target is None:
+            return w
+        return target
+
     def analyze(self):
         self.checkAndCreateFirstSymbol()
 
@@ -76,30 +145,55 @@ class SectionRodata(SectionBase):
         localOffset = 0
 
         partOfJumpTable = False
+        jumpTableTargetsRange: tuple[int, int]|None = None
+        jumpTableSym: common.ContextSymbol|None = None
+        jumpTableStart = 0
         for w in self.words:
             currentVram = self.getVramOffset(localOffset)
             contextSym = self.getSymbol(currentVram, tryPlusOffset=False)
+            target = w
 
             if contextSym is not None and contextSym.isJumpTable():
                 partOfJumpTable = True
+                jumpTableTargetsRange = contextSym.jumpTableTargetsRange
+                jumpTableSym = contextSym
+                jumpTableStart = localOffset
+                if not contextSym.isGpRelJumpTable:
+                    contextSym.isGpRelJumpTable = self._isGpRelJumpTableEntry(w, jumpTableTargetsRange)
+                target = self._resolveJumpTableEntry(w, contextSym)
 
             elif partOfJumpTable:
+                target = self._resolveJumpTableEntry(w, jumpTableSym)
+
                 if localOffset in self.pointersOffsets:
                     partOfJumpTable = True
 
                 elif contextSym is not None:
                     partOfJumpTable = False
 
-                elif ((w >> 24) & 0xFF) != 0x80:
+                elif ((target >> 24) & 0xFF) != 0x80:
                     partOfJumpTable = False
 
+                elif jumpTableTargetsRange is not None and not (jumpTableTargetsRange[0] <= target < jumpTableTargetsRange[1]):
+                    # A jump table can only point to the function using it
+                    partOfJumpTable = False
+
+            if partOfJumpTable and target % 4 != 0:
+                # Instructions are always aligned, so this can't be part of a jump table
+                common.Utils.epprintQuietless(f"Warning: The jump table entry at 0x{currentVram:08X} (in '{self.name}') points to the unaligned address 0x{target:08X}. Ending the jump table there")
+                partOfJumpTable = False
+
             if partOfJumpTable:
-                labelSym = self.addJumpTableLabel(w, isAutogenerated=True)
+                labelSym = self.addJumpTableLabel(target, isAutogenerated=True)
                 labelSym.referenceCounter += 1
+                if jumpTableSym is not None:
+                    labelSym.addJumpTableCase(jumpTableSym, (localOffset - jumpTableStart) // 4)
+                labelSym.setOriginIfUnset(common.SymbolOrigin.jumpTable, self.getVromOffset(localOffset))
 
             elif self.popPointerInDataReference(currentVram) is not None:
                 if common.GlobalConfig.ADD_NEW_SYMBOLS:
                     contextSym = self.addSymbol(currentVram, self.sectionType, isAutogenerated=True)
+                    contextSym.setOriginIfUnset(common.SymbolOrigin.dataPointer)
                     contextSym.isMaybeString = self._stringGuesser(contextSym, localOffset)
 
             elif contextSym is not None:
@@ -113,6 +207,8 @@ class SectionRodata(SectionBase):
 
             localOffset += 4
 
+        self.checkAutogeneratedSymbolsLimit()
+
         previousSymbolWasLateRodata = False
         previousSymbolExtraPadding = 0
 
@@ -123,6 +219,10 @@ class SectionRodata(SectionBase):
                 nextOffset = symbolList[i+1][0]
                 words = self.words[offset//4:nextOffset//4]
 
+            contextSym = self.getSymbol(vram, tryPlusOffset=False)
+            if contextSym is not None:
+                contextSym.isMaybeDouble = self._doubleGuesser(contextSym, offset, len(words)*4)
+
             vrom = self.getVromOffset(offset)
             vromEnd = vrom + len(words)*4
             sym = symbols.SymbolRodata(self.context, vrom, vromEnd, offset + self.inFileOffset, vram, words, self.segmentVromStart, self.overlayCategory)
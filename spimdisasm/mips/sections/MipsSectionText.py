@@ -16,9 +16,17 @@ from . import SectionBase
 
 
 class SectionText(SectionBase):
-    def __init__(self, context: common.Context, vromStart: int, vromEnd: int, vram: int, filename: str, array_of_bytes: bytearray, segmentVromStart: int, overlayCategory: str|None):
+    def __init__(self, context: common.Context, vromStart: int, vromEnd: int, vram: int, filename: str, array_of_bytes: common.Utils.BytesBuffer, segmentVromStart: int, overlayCategory: str|None):
         super().__init__(context, vromStart, vromEnd, vram, filename, array_of_bytes, common.FileSectionType.Text, segmentVromStart, overlayCategory)
 
+        self.dataHoles: common.IntervalMap[None] = common.IntervalMap()
+        "Ranges of vram which are data embedded in the code, so those are never disassembled as instructions"
+
+        self.expectedFunctionCount: int|None = None
+        "Amount of functions this section is expected to have. `analyze` warns if the amount of functions found differs too much from it"
+        self.expectedFunctionCountTolerance: float = 0.5
+        "How much the amount of functions found can differ from `expectedFunctionCount`, as a fraction of it"
+
 
     @property
     def nFuncs(self) -> int:
@@ -40,11 +48,55 @@ class SectionText(SectionBase):
             instrsList.append(instr)
         return instrsList
 
+    def addDataHole(self, vram: int, size: int) -> None:
+        """Marks the [`vram`, `vram + size`) range as data, like an inline table of handwritten code.
+
+        The range is emitted as data and splits the function it is placed on, with the code resuming right after the range.
+        Data holes can't overlap each other. This must be called before `analyze`."""
+        if vram % 4 != 0 or size % 4 != 0 or size <= 0:
+            raise RuntimeError(f"Data hole 0x{vram:08X} (size 0x{size:X}) must be word aligned")
+        if vram < self.vram or vram + size > self.vramEnd:
+            raise RuntimeError(f"Data hole 0x{vram:08X} (size 0x{size:X}) is outside of the section '{self.name}'")
+        self.dataHoles.insertRange(vram, vram + size, None)
+
+    def getDataHoleEnd(self, localOffset: int) -> int|None:
+        "Returns the local offset of the end of the data hole starting at `localOffset`, or `None` if no data hole starts there"
+        vram = self.getVramOffset(localOffset)
+        hole = self.dataHoles.getRange(vram)
+        if hole is None or hole[0] != vram:
+            return None
+        return hole[1] - self.vram
+
+    def _isTrustableFunctionStart(self, contextSym: common.ContextSymbol|None) -> bool:
+        return contextSym is not None and contextSym.getType() == common.SymbolSpecialType.function.toStr() and contextSym.isTrustableFunction(self.isRsp)
+
+    def _isTailCall(self, target: int, functionStart: int, instructionOffset: int) -> bool:
+        """Checks if an unconditional `j` or `b` at `instructionOffset` leaves the function starting at `functionStart` for good.
+
+        It is a tail call only if the target is the start of a known function, lies outside of this section, or lies past the next known function"""
+        if self.getVramOffset(functionStart) <= target <= self.getVramOffset(instructionOffset):
+            # Local label of the current function
+            return False
+
+        if self._isTrustableFunctionStart(self.getSymbol(target, tryPlusOffset=False)):
+            return True
+        if target < self.vram or target >= self.vramEnd:
+            return True
+
+        for symVram, contextSym in self.getSymbolsRange(self.getVramOffset(instructionOffset + 8), self.vramEnd):
+            if self._isTrustableFunctionStart(contextSym):
+                return target >= symVram
+        return False
+
     def analyze(self):
         functionEnded = False
         farthestBranch = 0
         funcsStartsList = [0]
         unimplementedInstructionsFuncList = []
+        dataHolesStarts: set[int] = set()
+        "Indices of funcsStartsList entries which are data holes"
+        holesBoundaries: set[int] = set()
+        "Instruction indices where a data hole starts or ends. Function starts placed there are never removed"
 
         instrsList = self.wordListToInstructions(self.words, self.getVramOffset(0), self.isRsp)
 
@@ -72,7 +124,7 @@ class SectionText(SectionBase):
                 # Loop over until we find a instruction that isn't a nop
                 while index < nInstr:
                     instr = instrsList[index]
-                    if not instr.isNop():
+                    if not instr.isNop() or self.getDataHoleEnd(instructionOffset) is not None:
                         if isboundary:
                             self.fileBoundaries.append(self.inFileOffset + index*4)
                         break
@@ -90,12 +142,49 @@ class SectionText(SectionBase):
                 instr = instrsList[index]
                 isInstrImplemented = instr.isImplemented()
 
+            holeEnd = self.getDataHoleEnd(instructionOffset)
+            if holeEnd is not None:
+                # The data hole is never decoded and splits the current function
+                if funcsStartsList[-1] != index:
+                    funcsStartsList.append(index)
+                    unimplementedInstructionsFuncList.append(not isInstrImplemented)
+                dataHolesStarts.add(len(funcsStartsList) - 1)
+                holesBoundaries.add(index)
+
+                index = holeEnd // 4
+                instructionOffset = holeEnd
+                funcsStartsList.append(index)
+                unimplementedInstructionsFuncList.append(True)
+                holesBoundaries.add(index)
+
+                # Start a new function after the hole
+                farthestBranch = 0
+                isInstrImplemented = True
+                isLikelyHandwritten = self.isHandwritten
+                currentInstructionStart = instructionOffset
+                currentFunctionSym = self.getSymbol(self.getVramOffset(instructionOffset), tryPlusOffset=False)
+                continue
+
             currentVram = self.getVramOffset(instructionOffset)
 
             if not self.isRsp and not isLikelyHandwritten:
                 isLikelyHandwritten = instr.isLikelyHandwritten()
 
-            if instr.isBranch() or instr.isUnconditionalBranch():
+            isTailCall = False
+            isLocalJump = False
+            if instr.uniqueId == rabbitizer.InstrId.cpu_j:
+                # Checked before `--j-branch` can make the target extend the function as a branch would
+                leavesFunction = self._isTailCall(instr.getInstrIndexAsVram(), currentInstructionStart, instructionOffset)
+                # Branches found before this one must not go past it
+                isTailCall = leavesFunction and farthestBranch <= 0
+                isLocalJump = not leavesFunction
+            elif farthestBranch <= 0 and instr.isUnconditionalBranch():
+                isTailCall = self._isTailCall(currentVram + instr.getGenericBranchOffset(currentVram), currentInstructionStart, instructionOffset)
+
+            if isTailCall:
+                # The target belongs to another function, so it doesn't extend the current one
+                functionEnded = True
+            elif instr.isBranch() or (instr.isUnconditionalBranch() and instr.uniqueId != rabbitizer.InstrId.cpu_j) or isLocalJump:
                 branchOffset = instr.getGenericBranchOffset(currentVram)
                 if branchOffset > farthestBranch:
                     # keep track of the farthest branch target
@@ -108,6 +197,9 @@ class SectionText(SectionBase):
                     if not isLikelyHandwritten:
                         j = len(funcsStartsList) - 1
                         while j >= 0:
+                            if funcsStartsList[j] in holesBoundaries:
+                                # Functions can't extend over a data hole
+                                break
                             if (branchOffset + instructionOffset) < funcsStartsList[j] * 4:
                                 vram = self.getVramOffset(funcsStartsList[j]*4)
                                 funcSymbol = self.getSymbol(vram, tryPlusOffset=False)
@@ -120,13 +212,14 @@ class SectionText(SectionBase):
                                 break
                             j -= 1
 
-            elif instr.isJType():
+            if instr.isJType() and not isLocalJump:
                 target = instr.getInstrIndexAsVram()
                 if not self.isRsp:
                     if target >= 0x84000000:
                         # RSP address space?
                         isLikelyHandwritten = True
-                self.addFunction(target, isAutogenerated=True)
+                funcSym = self.addFunction(target, isAutogenerated=True)
+                funcSym.setOriginIfUnset(common.SymbolOrigin.jump, self.getVromOffset(instructionOffset))
 
             if not (farthestBranch > 0) and instr.isJump():
                 if instr.isJrRa():
@@ -143,8 +236,8 @@ class SectionText(SectionBase):
                     functionEnded = True
 
             if currentFunctionSym is not None and currentFunctionSym.size is not None:
-                if instructionOffset + 8 == currentInstructionStart + currentFunctionSym.getSize():
-                    functionEnded = True
+                # The size given by the user is honored exactly, ignoring any other heuristic
+                functionEnded = instructionOffset + 8 == currentInstructionStart + currentFunctionSym.getSize()
 
             index += 1
             farthestBranch -= 4
@@ -152,6 +245,14 @@ class SectionText(SectionBase):
 
         unimplementedInstructionsFuncList.append(not isInstrImplemented)
 
+        # The section ended before the last function did
+        lastFunctionIsUnterminated = not functionEnded and funcsStartsList[-1] < nInstr
+        if lastFunctionIsUnterminated:
+            lastFuncVram = self.getVramOffset(funcsStartsList[-1]*4)
+            common.Utils.epprintQuietless(f"Warning: The function at 0x{lastFuncVram:08X} (in '{self.name}') does not end before the section does. The section's split point may be wrong.")
+            if common.GlobalConfig.UNTERMINATED_FUNCTIONS_AS_DATA:
+                unimplementedInstructionsFuncList[-1] = True
+
         i = 0
         startsCount = len(funcsStartsList)
         for startIndex in range(startsCount):
@@ -177,7 +278,10 @@ class SectionText(SectionBase):
             vrom = self.getVromOffset(localOffset)
             vromEnd = vrom + (end - start)*4
 
-            if common.GlobalConfig.DISASSEMBLE_UNKNOWN_INSTRUCTIONS or not hasUnimplementedIntrs:
+            isDataHole = startIndex in dataHolesStarts
+            if isDataHole:
+                self.addSymbol(vram, sectionType=self.sectionType, isAutogenerated=True, symbolVrom=vrom)
+            elif common.GlobalConfig.DISASSEMBLE_UNKNOWN_INSTRUCTIONS or not hasUnimplementedIntrs:
                 funcSymbol = self.addFunction(vram, isAutogenerated=True, symbolVrom=vrom)
             elif common.GlobalConfig.ADD_NEW_SYMBOLS:
                 self.addSymbol(vram, sectionType=self.sectionType, isAutogenerated=True, symbolVrom=vrom)
@@ -189,12 +293,75 @@ class SectionText(SectionBase):
             func.index = i
             func.pointersOffsets |= self.pointersOffsets
             func.hasUnimplementedIntrs = hasUnimplementedIntrs
+            func.isDataHole = isDataHole
             func.parent = self
             func.isRsp = self.isRsp
+            func.isUnterminated = lastFunctionIsUnterminated and startIndex + 1 == startsCount
             func.analyze()
             self.symbolList.append(func)
             i += 1
 
+        self.checkAutogeneratedSymbolsLimit()
+
+        if common.GlobalConfig.VALIDATE_STACK_FRAMES:
+            for frameWarning in self.validateStackFrames():
+                common.Utils.epprintQuietless(f"Warning: {frameWarning.getMessage()}")
+
+        functionCountWarning = self.checkFunctionCount()
+        if functionCountWarning is not None:
+            common.Utils.epprintQuietless(f"Warning: {functionCountWarning}")
+
+
+    def validateStackFrames(self) -> list[symbols.StackFrameWarning]:
+        "Returns a warning for every function whose stack pointer adjustments don't balance, sorted by address"
+        frameWarnings: list[symbols.StackFrameWarning] = list()
+        for func in self.symbolList:
+            if not isinstance(func, symbols.SymbolFunction):
+                continue
+            frameWarning = func.checkStackFrame()
+            if frameWarning is not None:
+                frameWarnings.append(frameWarning)
+        return frameWarnings
+
+    def checkFunctionCount(self) -> str|None:
+        """Compares the amount of functions found against `expectedFunctionCount`, returning a message if they differ by more than `expectedFunctionCountTolerance`.
+
+        A big difference usually means a wrong boundary, or data being disassembled as code"""
+        if self.expectedFunctionCount is None:
+            return None
+        functionCount = sum(1 for func in self.symbolList if isinstance(func, symbols.SymbolFunction))
+        if abs(functionCount - self.expectedFunctionCount) <= self.expectedFunctionCount * self.expectedFunctionCountTolerance:
+            return None
+        return f"Expected around {self.expectedFunctionCount} functions on '{self.name}' (0x{self.vromStart:X}-0x{self.vromEnd:X}), but {functionCount} were found"
+
+    def getFunctionRanges(self) -> list[tuple[int, int]]:
+        "Returns the `(vramStart, vramEnd)` range of each function found by `analyze`, sorted by address. Data holes are not included"
+        ranges: list[tuple[int, int]] = list()
+        for func in self.symbolList:
+            if isinstance(func, symbols.SymbolFunction) and func.isDataHole:
+                continue
+            ranges.append((func.vram, func.vramEnd))
+        return ranges
+
+    def getCodeDataRanges(self) -> list[tuple[int, int, bool]]:
+        """Returns the final code/data partition of this section, as a list of `(vramStart, vramEnd, isCode)` tuples sorted by address.
+
+        A function is considered to be data if it is going to be disassembled as data (i.e. it has unimplemented instructions or it is a data hole).
+        Contiguous ranges of the same kind are merged together."""
+        ranges: list[tuple[int, int, bool]] = list()
+        for func in self.symbolList:
+            isCode = True
+            if isinstance(func, symbols.SymbolFunction):
+                isCode = not func.isEmittedAsData()
+
+            if len(ranges) > 0:
+                prevStart, prevEnd, prevIsCode = ranges[-1]
+                if prevIsCode == isCode and prevEnd == func.vram:
+                    ranges[-1] = (prevStart, func.vramEnd, isCode)
+                    continue
+            ranges.append((func.vram, func.vramEnd, isCode))
+        return ranges
+
 
     def compareToFile(self, other: FileBase):
         result = super().compareToFile(other)
@@ -5,10 +5,11 @@
 
 from __future__ import annotations
 
-from .MipsSectionBase import SectionBase
+from .MipsSectionBase import SectionBase, SectionAnalysisError
 
 from .MipsSectionText import SectionText
 from .MipsSectionData import SectionData
 from .MipsSectionRodata import SectionRodata
 from .MipsSectionBss import SectionBss
 from .MipsSectionRelocZ64 import SectionRelocZ64, RelocEntry
+from .MipsSectionPassthrough import SectionPassthrough
@@ -9,6 +9,9 @@ from typing import Callable
 
 from ... import common
 
+from ..MipsRelocTypes import RelocTypes
+from ..MipsRelocInfo import RelocInfo
+
 
 class SymbolBase(common.ElementBase):
     def __init__(self, context: common.Context, vromStart: int, vromEnd: int, inFileOffset: int, vram: int, words: list[int], sectionType: common.FileSectionType, segmentVromStart: int, overlayCategory: str|None):
@@ -20,8 +23,12 @@ class SymbolBase(common.ElementBase):
         contextSym.vromAddress = self.vromStart
         contextSym.isDefined = True
         contextSym.sectionType = self.sectionType
+        contextSym.autodetectedSize = len(words) * 4
         self.contextSym: common.ContextSymbol = contextSym
 
+        self.nonPointerOffsets: set[int] = set()
+        "Local offsets of the words which are never emitted as references to symbols, even if they look like pointers"
+
 
     def getName(self) -> str:
         return self.contextSym.getName()
@@ -47,9 +54,12 @@ class SymbolBase(common.ElementBase):
 
         wordValueHex = ""
         if wordValue is not None:
-            wordValueHex = f"{common.Utils.beWordToCurrenEndian(wordValue):08X} "
+            wordValueHex = f"{common.Utils.beWordToCurrenEndian(wordValue, self.getEndian()):08X} "
 
-        return f"/* {offsetHex} {vramHex} {wordValueHex}*/"
+        comment = common.GlobalConfig.ASM_COMMENT_FORMAT.format(rom=offsetHex, vram=vramHex, bytes=wordValueHex)
+        if common.GlobalConfig.ASM_COMMENT_OWN_LINE:
+            comment += common.GlobalConfig.LINE_ENDS
+        return comment
 
     def getSymbolAtVramOrOffset(self, localOffset: int) -> common.ContextSymbol|None:
         contextSym = self.context.getOffsetSymbol(self.inFileOffset + localOffset, self.sectionType)
@@ -76,9 +86,26 @@ class SymbolBase(common.ElementBase):
         pass
 
 
+    def getArrayIndexComment(self, localOffset: int) -> str:
+        "Returns a comment with the index of the array element at `localOffset`, or an empty string if this symbol isn't an array"
+        if not common.GlobalConfig.ASM_COMMENT or self.contextSym.arrayCount is None:
+            return ""
+        elementSize = self.contextSym.getArrayElementSize()
+        if elementSize is None or localOffset % elementSize != 0:
+            return ""
+        index = localOffset // elementSize
+        if index >= self.contextSym.arrayCount:
+            return ""
+        return f" # [{index}]"
+
     def analyze(self):
         self.renameBasedOnType()
 
+        if self.contextSym.arrayCount is not None and self.contextSym.size is not None:
+            elementSize = self.contextSym.getTypeSize()
+            if elementSize is not None and elementSize * self.contextSym.arrayCount != self.contextSym.size:
+                common.Utils.epprintQuietless(f"Warning: Symbol '{self.getName()}' is declared as an array of {self.contextSym.arrayCount} elements of type '{self.contextSym.getType()}' (0x{elementSize * self.contextSym.arrayCount:X} bytes), but its size is 0x{self.contextSym.size:X}")
+
         byteStep = 4
         if self.contextSym.isByte():
             byteStep = 1
@@ -105,23 +132,43 @@ class SymbolBase(common.ElementBase):
         localOffset = 4*i
         w = self.words[i]
 
-        isByte = False
-        isShort = False
-        if self.contextSym.isByte():
-            isByte = True
-        elif self.contextSym.isShort():
-            isShort = True
+        if self.contextSym.isString():
+            stringWord = self.getNthWordAsString(i)
+            if stringWord is not None:
+                dotType, value, skip = stringWord
+                label = ""
+                if i != 0:
+                    contextSym = self.getSymbolAtVramOrOffset(localOffset)
+                    if contextSym is not None:
+                        label = common.GlobalConfig.LINE_ENDS + contextSym.getSymbolLabel() + common.GlobalConfig.LINE_ENDS
+                return f"{label}{self.generateAsmLineComment(localOffset)} {dotType} {value}" + common.GlobalConfig.LINE_ENDS, skip
 
-        dotType = ".word"
         byteStep = 4
-        if isByte:
-            dotType = ".byte"
+        if self.contextSym.isByte():
             byteStep = 1
-        elif isShort:
-            dotType = ".short"
+        elif self.contextSym.isShort():
             byteStep = 2
 
-        for j in range(0, 4, byteStep):
+        # Size of each element of this word
+        elementSizes = [byteStep] * (4 // byteStep)
+        if self.contextSym.structLayout is not None:
+            elementSizes = []
+            j = 0
+            while j < 4:
+                elementSize = self.contextSym.getStructElementSize(localOffset + j)
+                elementSizes.append(elementSize)
+                j += elementSize
+
+        j = 0
+        for elementSize in elementSizes:
+            isByte = elementSize == 1
+            isShort = elementSize == 2
+            dotType = ".word"
+            if isByte:
+                dotType = ".byte"
+            elif isShort:
+                dotType = ".short"
+
             label = ""
             if j != 0 or i != 0:
                 contextSym = self.getSymbolAtVramOrOffset(localOffset+j)
@@ -145,9 +192,9 @@ class SymbolBase(common.ElementBase):
                     possibleReference = self.context.getRelocSymbol(self.inFileOffset + localOffset, self.sectionType)
                     if possibleReference is not None:
                         value = possibleReference.getNamePlusOffset(w)
-                else:
+                elif not self.contextSym.isInteger() and localOffset not in self.nonPointerOffsets and self.context.shouldCreateReference(self.getVramOffset(localOffset), w, RelocTypes.R_MIPS_32.name):
                     # This word could be a reference to a symbol
-                    symbolRef = self.getSymbol(w, tryPlusOffset=canReferenceSymbolsWithAddends)
+                    symbolRef = self.getSymbolForDataReference(w, canReferenceSymbolsWithAddends)
                     if symbolRef is not None:
                         value = symbolRef.getSymbolPlusOffset(w)
                     elif canReferenceConstants:
@@ -157,31 +204,204 @@ class SymbolBase(common.ElementBase):
 
             comment = self.generateAsmLineComment(localOffset+j)
             output += f"{label}{comment} {dotType} {value}"
+            output += self.getArrayIndexComment(localOffset+j)
             if j == 0 and i < len(self.endOfLineComment):
                 output += self.endOfLineComment[i]
             output += common.GlobalConfig.LINE_ENDS
 
+            j += elementSize
+
         return output, 0
 
 
+    def _decodeStringPool(self, buffer: bytearray, offset: int) -> tuple[list[tuple[int, str]], int]:
+        """Decodes the strings placed right after the string which ends at `offset`.
+
+        Returns a list of `(offset, decodedString)` pairs and the offset where the pool ends.
+        The pool is trimmed so it is only followed by padding up to the next word."""
+        pool: list[tuple[int, str, int]] = list()
+        while offset < len(buffer) and buffer[offset] != 0:
+            try:
+                decodedValue, rawStringSize = common.Utils.decodeString(buffer, offset, self.contextSym.getStringEncoding())
+            except (UnicodeDecodeError, RuntimeError):
+                break
+            pool.append((offset, decodedValue, offset + rawStringSize + 1))
+            offset += rawStringSize + 1
+
+        while len(pool) > 0:
+            poolEnd = pool[-1][2]
+            alignedEnd = min((poolEnd + 3) & ~3, len(buffer))
+            if all(x == 0 for x in buffer[poolEnd:alignedEnd]):
+                return [(strOffset, decodedValue) for strOffset, decodedValue, _ in pool], poolEnd
+            pool.pop()
+        return [], offset
+
+    def getNthWordAsString(self, i: int) -> tuple[str, str, int]|None:
+        """Decodes the string starting at the `i`th word, returning its directive, its value (including the directives of the padding which follows it)
+        and how many extra words it covers. Returns `None` if there's no valid string there"""
+        try:
+            buffer = bytearray(4*len(self.words))
+            common.Utils.beWordsToBytes(self.words, buffer)
+            # Strings must end before the next symbol starts
+            stringBoundary = self.getNextSymbolLocalOffset(4*i)
+            stringBuffer = buffer[:stringBoundary]
+            isUnterminated = self.contextSym.isUnterminatedString
+            decodedValue, rawStringSize = common.Utils.decodeString(stringBuffer, 4*i, self.contextSym.getStringEncoding(), allowUnterminated=isUnterminated)
+        except (UnicodeDecodeError, RuntimeError):
+            # Not a string
+            return None
+
+        if isUnterminated and 4*i + rawStringSize < stringBoundary:
+            # A NUL was found before the next symbol, so this string is terminated after all
+            isUnterminated = False
+        dotType = ".ascii" if isUnterminated else ".asciz"
+        value = f'"{decodedValue}"'
+        stringEnd = 4*i + rawStringSize + (0 if isUnterminated else 1)
+        if common.GlobalConfig.ASM_STRING_POOLS and not isUnterminated:
+            poolStrings, poolEnd = self._decodeStringPool(stringBuffer, stringEnd)
+            if len(poolStrings) > 0:
+                for poolOffset, poolString in poolStrings:
+                    value += common.GlobalConfig.LINE_ENDS + f'{self.generateAsmLineComment(poolOffset)} .asciz "{poolString}"'
+                stringEnd = poolEnd
+        alignedEnd = (stringEnd + 3) & ~3
+        if stringBoundary < alignedEnd:
+            # A symbol starts before the next word, so the remaining bytes can't be covered by an alignment directive
+            for byteOffset in range(stringEnd, alignedEnd):
+                byteLabel = ""
+                byteSym = self.getSymbolAtVramOrOffset(byteOffset)
+                if byteSym is not None:
+                    byteLabel = common.GlobalConfig.LINE_ENDS + byteSym.getSymbolLabel() + common.GlobalConfig.LINE_ENDS
+                value += common.GlobalConfig.LINE_ENDS + f"{byteLabel}{self.generateAsmLineComment(byteOffset)} .byte 0x{buffer[byteOffset]:02X}"
+        else:
+            value += common.GlobalConfig.LINE_ENDS + (22 * " ") + ".balign 4"
+        return dotType, value, (stringEnd - 1) // 4 - i
+
+    def getRelocations(self, canReferenceSymbolsWithAddends: bool|None=None) -> list[RelocInfo]:
+        "Returns the relocations needed by the words of this symbol which reference other symbols"
+        relocs: list[RelocInfo] = list()
+
+        if self.contextSym.isByte() or self.contextSym.isShort():
+            return relocs
+        if self.contextSym.isString() or self.contextSym.isFloat() or self.contextSym.isDouble() or self.contextSym.isInteger():
+            return relocs
+
+        if canReferenceSymbolsWithAddends is None:
+            canReferenceSymbolsWithAddends = self.canUseAddendsOnData()
+
+        for i, w in enumerate(self.words):
+            localOffset = 4*i
+
+            if self.contextSym.getStructElementSize(localOffset) != 4:
+                # Only whole words can be references
+                continue
+
+            if len(self.context.relocSymbols[self.sectionType]) > 0:
+                # .elf relocated symbol
+                possibleReference = self.context.getRelocSymbol(self.inFileOffset + localOffset, self.sectionType)
+                if possibleReference is not None:
+                    relocs.append(RelocInfo(self.inFileOffset + localOffset, RelocTypes.R_MIPS_32, possibleReference.getName(), w))
+                continue
+
+            if localOffset in self.nonPointerOffsets:
+                continue
+            if not self.context.shouldCreateReference(self.getVramOffset(localOffset), w, RelocTypes.R_MIPS_32.name):
+                continue
+
+            symbolRef = self.getSymbolForDataReference(w, canReferenceSymbolsWithAddends)
+            if symbolRef is not None:
+                relocs.append(RelocInfo(self.inFileOffset + localOffset, RelocTypes.R_MIPS_32, symbolRef.getName(), w - symbolRef.vram))
+
+        return relocs
+
+
+    def referencesVramRange(self, vramStart: int, vramEnd: int) -> bool:
+        "Checks if any word of this symbol looks like a reference to the [`vramStart`, `vramEnd`) range"
+        for w in self.words:
+            if vramStart <= w < vramEnd:
+                return True
+        return False
+
     def countExtraPadding(self) -> int:
         "Returns how many extra word paddings this symbol has"
         return 0
 
 
+    def getAlignment(self) -> int:
+        "Returns the alignment in bytes this symbol should be emitted with, or 0 if no alignment directive is needed"
+        if self.contextSym.alignment is not None:
+            return self.contextSym.alignment
+
+        alignment = common.GlobalConfig.ASM_DATA_SYMBOLS_ALIGNMENT
+        if alignment > 0 and self.contextSym.isDouble():
+            alignment = max(alignment, 8)
+        return alignment
+
+    def getAlignmentDirective(self) -> str:
+        alignment = self.getAlignment()
+        if alignment <= 1:
+            return ""
+        # The padding is computed by the assembler relative to the start of the section, which is aligned to 16 by the prelude
+        if self.vram % alignment != 0 or alignment > 16:
+            common.Utils.epprintQuietless(f"Warning: Symbol '{self.getName()}' (0x{self.vram:08X}) can't be aligned to {alignment} bytes. Skipping its alignment directive")
+            return ""
+        return f".balign {alignment}" + common.GlobalConfig.LINE_ENDS
+
+    def getSizeDirective(self) -> str:
+        if not common.GlobalConfig.ASM_SIZE_DIRECTIVE:
+            return ""
+        return common.GlobalConfig.ASM_SIZE_DIRECTIVE.replace("{name}", self.getName()) + common.GlobalConfig.LINE_ENDS
+
+    def getFillRunLength(self, i: int, canReferenceSymbolsWithAddends: bool) -> int:
+        "Returns how many words starting at the `i`th word can be emitted as a single `.fill` directive, or 0 if those shouldn't be collapsed"
+        threshold = common.GlobalConfig.ASM_DATA_FILL_THRESHOLD
+        if threshold <= 0:
+            return 0
+        if self.contextSym.isString() or self.contextSym.isJumpTable():
+            return 0
+
+        w = self.words[i]
+        hasRelocs = len(self.context.relocSymbols[self.sectionType]) > 0
+        count = 0
+        while i + count < self.sizew and self.words[i + count] == w:
+            localOffset = 4*(i + count)
+            if i + count != 0 and any(self.getSymbolAtVramOrOffset(localOffset + j) is not None for j in range(4)):
+                # Runs never span over labels, not even the ones at the start of the run
+                break
+            if i + count < len(self.endOfLineComment) and self.endOfLineComment[i + count] != "":
+                break
+            if hasRelocs and self.context.getRelocSymbol(self.inFileOffset + localOffset, self.sectionType) is not None:
+                break
+            if w != 0 and not self.contextSym.isInteger() and self.getSymbolForDataReference(w, canReferenceSymbolsWithAddends) is not None:
+                break
+            count += 1
+
+        if count * 4 < threshold:
+            return 0
+        return count
+
     def disassembleAsData(self) -> str:
-        output = self.getLabel()
+        output = self.getAlignmentDirective()
+        output += self.getLabel()
 
         canReferenceSymbolsWithAddends = self.canUseAddendsOnData()
         canReferenceConstants = self.canUseConstantsOnData()
 
         i = 0
         while i < self.sizew:
+            fillCount = self.getFillRunLength(i, canReferenceSymbolsWithAddends)
+            if fillCount > 0:
+                output += self.generateAsmLineComment(4*i)
+                output += f" .fill 0x{fillCount:X}, 4, 0x{self.words[i]:08X}" + common.GlobalConfig.LINE_ENDS
+                i += fillCount
+                continue
+
             data, skip = self.getNthWord(i, canReferenceSymbolsWithAddends, canReferenceConstants)
             output += data
 
             i += skip
             i += 1
+
+        output += self.getSizeDirective()
         return output
 
     def disassemble(self) -> str:
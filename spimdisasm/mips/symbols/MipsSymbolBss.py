@@ -15,16 +15,31 @@ class SymbolBss(SymbolBase):
         super().__init__(context, vromStart, vromEnd, inFileOffset, vram, list(), common.FileSectionType.Bss, segmentVromStart, overlayCategory)
 
         self.spaceSize: int = spaceSize
+        self.contextSym.autodetectedSize = spaceSize
 
 
     @property
     def sizew(self) -> int:
         return self.spaceSize // 4
 
+    def disassembleAsCommonSymbol(self) -> str:
+        output = self.generateAsmLineComment(0)
+        output += f" .comm {self.getName()}, 0x{self.spaceSize:02X}"
+        alignment = self.getAlignment()
+        if alignment > 1:
+            output += f", {alignment}"
+        output += common.GlobalConfig.LINE_ENDS
+        return output
+
     def disassembleAsBss(self) -> str:
-        output = self.getLabel()
+        if common.GlobalConfig.ASM_BSS_AS_COMMON_SYMBOLS:
+            return self.disassembleAsCommonSymbol()
+
+        output = self.getAlignmentDirective()
+        output += self.getLabel()
         output += self.generateAsmLineComment(0)
         output += f" .space 0x{self.spaceSize:02X}" + common.GlobalConfig.LINE_ENDS
+        output += self.getSizeDirective()
         return output
 
     def disassemble(self) -> str:
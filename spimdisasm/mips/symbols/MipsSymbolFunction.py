@@ -5,19 +5,73 @@
 
 from __future__ import annotations
 
+import dataclasses
+import re
 import rabbitizer
 
 from ... import common
 
+from ..InstructionConfig import InstructionConfig
+from ..MipsRelocInfo import RelocInfo
+from ..MipsRelocTypes import RelocTypes
+
 from . import SymbolText, analysis
 
 
+@dataclasses.dataclass
+class StackFrameWarning:
+    "The stack pointer adjustments of a function don't balance, which usually means the function boundaries are wrong"
+
+    functionName: str
+    vram: int
+    allocatedSize: int|None
+    "Size reserved by the prologue (`addiu $sp, $sp, -N`), or `None` if the function has no prologue"
+    releasedSizes: list[int]
+    "Size released by each epilogue (`addiu $sp, $sp, N`) of the function"
+
+    def getMessage(self) -> str:
+        if self.allocatedSize is None:
+            return f"The function '{self.functionName}' (0x{self.vram:08X}) releases a stack frame it never allocated. The start of the function may be wrong"
+        if len(self.releasedSizes) == 0:
+            return f"The function '{self.functionName}' (0x{self.vram:08X}) allocates a stack frame of 0x{self.allocatedSize:X} bytes but never releases it. The end of the function may be wrong"
+        releasedStr = ", ".join(f"0x{size:X}" for size in self.releasedSizes)
+        return f"The function '{self.functionName}' (0x{self.vram:08X}) allocates a stack frame of 0x{self.allocatedSize:X} bytes but releases {releasedStr} bytes. The function boundaries may be wrong"
+
+
 class SymbolFunction(SymbolText):
+    AtRegisterPattern = re.compile(r"\$(at|1)\b")
+    "Matches `$at` in both its named and numeric forms. Other registers numbered 1 (like `$f1`) don't match"
+
+    SllHintsMnemonics: dict[int, str] = {
+        0x00000040: "ssnop", # sll $zero, $zero, 1
+        0x000000C0: "ehb",   # sll $zero, $zero, 3
+        0x00000140: "pause", # sll $zero, $zero, 5
+    }
+
+    CacheOperationNames: dict[int, tuple[str, str]] = {
+        0: ("index_invalidate", "index_writeback_invalidate"),
+        1: ("index_load_tag", "index_load_tag"),
+        2: ("index_store_tag", "index_store_tag"),
+        3: ("create_dirty_exclusive", "create_dirty_exclusive"),
+        4: ("hit_invalidate", "hit_invalidate"),
+        5: ("fill", "hit_writeback_invalidate"),
+        6: ("hit_writeback", "hit_writeback"),
+        7: ("hit_set_virtual", "hit_set_virtual"),
+    }
+    "Names of the operations of the `cache` instruction, as `(instructionCacheName, dataCacheName)`"
+    CacheNames: dict[int, str] = {
+        0: "icache",
+        1: "dcache",
+        2: "sicache",
+        3: "sdcache",
+    }
+
     def __init__(self, context: common.Context, vromStart: int, vromEnd: int, inFileOffset: int, vram: int, instrsList: list[rabbitizer.Instruction], segmentVromStart: int, overlayCategory: str|None):
         super().__init__(context, vromStart, vromEnd, inFileOffset, vram, list(), segmentVromStart, overlayCategory)
         self.instructions = list(instrsList)
+        self.contextSym.autodetectedSize = len(self.instructions) * 4
 
-        self.instrAnalyzer = analysis.InstrAnalyzer(self.vram)
+        self.instrAnalyzer = analysis.InstrAnalyzer(self.vram, self.vramEnd)
 
         self.branchesTaken: set[int] = set()
 
@@ -27,6 +81,14 @@ class SymbolFunction(SymbolText):
         self.hasUnimplementedIntrs: bool = False
         self.isRsp: bool = False
         self.isLikelyHandwritten: bool = False
+        self.isUnterminated: bool = False
+        "The section ended before this function returned"
+        self.isDataHole: bool = False
+        "This chunk was marked by the user as data embedded in the code, so it is always emitted as data"
+        self.rejectedReferences: set[int] = set()
+        "Offsets of the instructions whose references were vetoed by `Context.referenceFilter`, which are emitted with their raw immediates"
+        self.hasUnalignedTargets: bool = False
+        "An indirect call of this function targets an address not aligned to 4 bytes, so this is likely data decoded as code"
 
     @property
     def nInstr(self) -> int:
@@ -116,8 +178,40 @@ class SymbolFunction(SymbolText):
             instructionOffset += 4
 
 
+    def _discardGpRelOutsideSegments(self):
+        "gp relative accesses which don't land on any known segment are kept as raw offsets instead of producing new symbols"
+        for loOffset, symVram in list(self.instrAnalyzer.symbolGpInstrOffset.items()):
+            if self.getSymbol(symVram) is not None:
+                continue
+            if self.getSegmentForVram(symVram) is not self.context.unknownSegment:
+                continue
+
+            common.Utils.epprintQuietless(f"Warning: The gp relative access at 0x{self.getVramOffset(loOffset):08X} (function '{self.getName()}') points to 0x{symVram:08X}, which is outside of every known segment. Keeping it as a raw offset")
+            del self.instrAnalyzer.symbolGpInstrOffset[loOffset]
+            self.instrAnalyzer.symbolLoInstrOffset.pop(loOffset, None)
+            self.instrAnalyzer.symbolInstrOffset.pop(loOffset, None)
+            self.instrAnalyzer.referencedVramsInstrOffset.pop(loOffset, None)
+
+    def _checkUnalignedTargets(self) -> bool:
+        """Reports every indirect call (a `jalr` to an address loaded with `lui`/`addiu`) which targets an address not aligned to 4 bytes. Returns `True` if any was found
+
+        Branches and jumps encode their targets in words, so only the addresses loaded into registers can be unaligned"""
+        for instrOffset, targetVram in self.instrAnalyzer.indirectFuncCallInstrOffsets.items():
+            if targetVram % 4 != 0:
+                common.Utils.epprintQuietless(f"Warning: The instruction at 0x{self.getVramOffset(instrOffset):08X} (function '{self.getName()}') calls the unaligned address 0x{targetVram:08X}. This function may be data decoded as code")
+                self.hasUnalignedTargets = True
+        return self.hasUnalignedTargets
+
+    def isEmittedAsData(self) -> bool:
+        "Checks if this function is going to be disassembled as data instead of instructions"
+        if self.isDataHole:
+            return True
+        if self.hasUnalignedTargets and common.GlobalConfig.UNALIGNED_TARGETS_AS_DATA:
+            return True
+        return not common.GlobalConfig.DISASSEMBLE_UNKNOWN_INSTRUCTIONS and self.hasUnimplementedIntrs
+
     def analyze(self):
-        if not common.GlobalConfig.DISASSEMBLE_UNKNOWN_INSTRUCTIONS and self.hasUnimplementedIntrs:
+        if self.isEmittedAsData():
             offset = 0
             for instr in self.instructions:
                 currentVram = self.getVramOffset(offset)
@@ -158,24 +252,61 @@ class SymbolFunction(SymbolText):
 
         self._processElfRelocSymbols()
 
+        if self._checkUnalignedTargets() and self.isEmittedAsData():
+            return
+
         # Branches
         for instrOffset, targetBranchVram in self.instrAnalyzer.branchInstrOffsets.items():
+            if targetBranchVram % 4 != 0:
+                continue
             branch = self.instrAnalyzer.branchTargetInstrOffsets[instrOffset]
             labelSym = self.addBranchLabel(targetBranchVram, isAutogenerated=True, symbolVrom=self.getVromOffset(branch))
             labelSym.referenceCounter += 1
+            labelSym.setOriginIfUnset(common.SymbolOrigin.branch, self.getVromOffset(instrOffset))
 
         # Function calls
-        for targetVram in self.instrAnalyzer.funcCallInstrOffsets.values():
+        for instrOffset, targetVram in self.instrAnalyzer.funcCallInstrOffsets.items():
+            if targetVram % 4 != 0:
+                continue
+            if not self.context.shouldCreateReference(self.getVramOffset(instrOffset), targetVram, RelocTypes.R_MIPS_26.name):
+                self.rejectedReferences.add(instrOffset)
+                continue
+            if self.context.isAddressIgnored(targetVram):
+                common.Utils.epprintQuietless(f"Warning: The instruction at 0x{self.getVramOffset(instrOffset):08X} (function '{self.getName()}') calls 0x{targetVram:08X}, which is inside an ignored range. Keeping it as a raw address")
+                continue
+            funcSym = self.addFunction(targetVram, isAutogenerated=True)
+            funcSym.referenceCounter += 1
+            funcSym.setOriginIfUnset(common.SymbolOrigin.jump, self.getVromOffset(instrOffset))
+            funcSym.addReferenceFunction(self.contextSym)
+
+        # Functions called through a register loaded with their address
+        for instrOffset, targetVram in self.instrAnalyzer.indirectFuncCallInstrOffsets.items():
+            if targetVram % 4 != 0 or self.context.isAddressIgnored(targetVram):
+                continue
+            if not common.GlobalConfig.ADD_NEW_SYMBOLS and self.getSymbol(targetVram, tryPlusOffset=False) is None:
+                continue
             funcSym = self.addFunction(targetVram, isAutogenerated=True)
             funcSym.referenceCounter += 1
+            funcSym.setOriginIfUnset(common.SymbolOrigin.jump, self.getVromOffset(instrOffset))
+            funcSym.addReferenceFunction(self.contextSym)
 
         if not self.isRsp and len(self.instrAnalyzer.funcCallOutsideRangesOffsets) > 0:
             self.isLikelyHandwritten = True
 
+        self._discardGpRelOutsideSegments()
+
         # Symbols
         for loOffset, symVram in self.instrAnalyzer.symbolLoInstrOffset.items():
             if symVram in self.context.bannedSymbols:
                 continue
+            if self.context.isAddressIgnored(symVram):
+                common.Utils.epprintQuietless(f"Warning: The instruction at 0x{self.getVramOffset(loOffset):08X} (function '{self.getName()}') references 0x{symVram:08X}, which is inside an ignored range. Keeping it as a raw address")
+                continue
+            if not self.context.shouldCreateReference(self.getVramOffset(loOffset), symVram, self.getRelocTypeForInstruction(self.instructions[loOffset//4], loOffset).name):
+                self.rejectedReferences.add(loOffset)
+                if loOffset in self.instrAnalyzer.lowToHiDict:
+                    self.rejectedReferences.add(self.instrAnalyzer.lowToHiDict[loOffset])
+                continue
 
             # Check for user-defined symbol patches
             patchedAddress = self.getLoPatch(self.getVramOffset(loOffset))
@@ -198,17 +329,116 @@ class SymbolFunction(SymbolText):
                                     contextSym = self.addSymbol(symVram, isAutogenerated=True)
 
             contextSym.referenceCounter += 1
+            contextSym.setOriginIfUnset(common.SymbolOrigin.hiLoPair, self.getVromOffset(loOffset))
+            contextSym.addReferenceFunction(self.contextSym)
             if symType is not None:
                 contextSym.setTypeIfUnset(symType)
+            if contextSym.address == symVram:
+                contextSym.accessTypes |= self.instrAnalyzer.symbolAccessTypes.get(symVram, set())
+                if symVram in self.instrAnalyzer.intToFloatSymbols:
+                    contextSym.isIntConvertedToFloat = True
+
+        self._typeStringArguments()
 
         # Jump tables
-        for targetVram in self.instrAnalyzer.jumpRegisterIntrOffset.values():
-            self.addJumpTable(targetVram, isAutogenerated=True)
+        for instrOffset, targetVram in self.instrAnalyzer.jumpRegisterIntrOffset.items():
+            jumpTableSym = self.addJumpTable(targetVram, isAutogenerated=True)
+            jumpTableSym.jumpTableTargetsRange = (self.vram, self.vramEnd)
+            jumpTableSym.setOriginIfUnset(common.SymbolOrigin.jumpTable, self.getVromOffset(instrOffset))
 
         for instr in self.instructions:
             instr.inHandwrittenFunction = self.isLikelyHandwritten
 
 
+    def _typeStringArguments(self) -> None:
+        "Types as strings the symbols passed on the string arguments of the functions listed on `Context.stringArgumentFunctions`"
+        if len(self.context.stringArgumentFunctions) == 0:
+            return
+
+        for instrOffset, targetVram in self.instrAnalyzer.funcCallInstrOffsets.items():
+            argumentIndices = self.context.stringArgumentFunctions.get(targetVram)
+            if argumentIndices is None:
+                continue
+            for argumentIndex in sorted(argumentIndices):
+                # The delay slot runs before the called function, so it may set the argument too
+                address = self._getGprValueBefore(instrOffset + 8, 4 + argumentIndex, ignoreLabelAtStart=True)
+                if address is None:
+                    continue
+                contextSym = self.getSymbol(address, tryPlusOffset=False)
+                if contextSym is None or contextSym.isUserDeclared:
+                    continue
+                if contextSym.setTypeIfUnset("char"):
+                    common.Utils.printVerbose(f"Typing '{contextSym.getName()}' as a string, since it is passed to 0x{targetVram:08X} by '{self.getName()}'")
+
+    def getRelocTypeForInstruction(self, instr: rabbitizer.Instruction, instructionOffset: int) -> RelocTypes:
+        if instr.isJType():
+            return RelocTypes.R_MIPS_26
+        if instr.canBeHi():
+            return RelocTypes.R_MIPS_HI16
+        if instructionOffset in self.instrAnalyzer.symbolGpInstrOffset:
+            return RelocTypes.R_MIPS_GPREL16
+        return RelocTypes.R_MIPS_LO16
+
+    def getRelocations(self, canReferenceSymbolsWithAddends: bool|None=None) -> list[RelocInfo]:
+        "Returns the relocations needed by the instructions of this function, matching the symbols used by `disassemble`"
+        relocs: list[RelocInfo] = list()
+
+        if self.isEmittedAsData():
+            return relocs
+
+        instructionOffset = 0
+        for instr in self.instructions:
+            offset = self.inFileOffset + instructionOffset
+
+            if len(self.context.relocSymbols[self.sectionType]) > 0:
+                # .elf relocated symbol
+                possibleReference = self.context.getRelocSymbol(offset, self.sectionType)
+                if possibleReference is not None:
+                    relocType = RelocTypes.fromValue(possibleReference.relocType)
+                    if relocType == RelocTypes.INVALID:
+                        relocType = self.getRelocTypeForInstruction(instr, instructionOffset)
+                    addend = self.instrAnalyzer.symbolInstrOffset.get(instructionOffset, 0) if instr.isIType() else 0
+                    relocs.append(RelocInfo(offset, relocType, possibleReference.getName(), addend))
+                instructionOffset += 4
+                continue
+
+            if instructionOffset in self.rejectedReferences:
+                instructionOffset += 4
+                continue
+
+            if instr.isJType():
+                funcSym = self.getSymbol(instr.getInstrIndexAsVram(), tryPlusOffset=False)
+                if funcSym is not None:
+                    relocs.append(RelocInfo(offset, RelocTypes.R_MIPS_26, funcSym.getName()))
+
+            elif instr.isIType() and not self.pointersRemoved and instructionOffset in self.instrAnalyzer.symbolInstrOffset:
+                address = self.instrAnalyzer.symbolInstrOffset[instructionOffset]
+                if not self.context.isAddressBanned(address):
+                    instrVram = self.getVramOffset(instructionOffset)
+                    if instr.canBeHi() and instructionOffset in self.instrAnalyzer.hiToLowDict:
+                        instrVram = self.getVramOffset(self.instrAnalyzer.hiToLowDict[instructionOffset])
+
+                    patchedAddress = self.getLoPatch(instrVram)
+                    if patchedAddress is not None:
+                        symbol = self.getSymbol(patchedAddress, tryPlusOffset=True, checkUpperLimit=False)
+                    else:
+                        symbol = self.getSymbol(address, tryPlusOffset=True)
+
+                    if symbol is not None:
+                        relocs.append(RelocInfo(offset, self.getRelocTypeForInstruction(instr, instructionOffset), symbol.getName(), address - symbol.vram))
+
+            instructionOffset += 4
+
+        return relocs
+
+    def referencesVramRange(self, vramStart: int, vramEnd: int) -> bool:
+        "Checks if any instruction of this function references the [`vramStart`, `vramEnd`) range"
+        targets = [*self.instrAnalyzer.referencedVrams, *self.instrAnalyzer.funcCallInstrOffsets.values(), *self.instrAnalyzer.branchInstrOffsets.values()]
+        for vram in targets:
+            if vramStart <= vram < vramEnd:
+                return True
+        return False
+
     def countExtraPadding(self) -> int:
         count = 0
         for i in range(len(self.instructions)-1, 0, -1):
@@ -225,6 +455,38 @@ class SymbolFunction(SymbolText):
         return count
 
 
+    def getTrailingPadding(self) -> tuple[int, int]|None:
+        """Returns the offset where the `nop` padding placed after the last `jr $ra` of this function starts and the alignment it pads to.
+
+        Returns `None` if there's no padding, if the function doesn't end on a `jr $ra` or if anything references the padding"""
+        paddingOffset = len(self.instructions) * 4
+        while paddingOffset > 8 and self.instructions[paddingOffset//4 - 1].isNop():
+            paddingOffset -= 4
+        if paddingOffset == len(self.instructions) * 4:
+            return None
+
+        if self.instructions[paddingOffset//4 - 1].isJrRa():
+            # The delay slot of the `jr $ra` is a `nop` too, so it was counted as padding
+            paddingOffset += 4
+        elif paddingOffset < 8 or not self.instructions[paddingOffset//4 - 2].isJrRa():
+            # Otherwise the last non-`nop` instruction must be the delay slot of the `jr $ra`
+            return None
+
+        if paddingOffset >= len(self.instructions) * 4:
+            return None
+        for offset in range(paddingOffset, len(self.instructions) * 4, 4):
+            if self.getLabelForOffset(offset) != "":
+                return None
+
+        # The padding is computed by the assembler relative to the start of the section, which is aligned to 16 by the prelude
+        sectionVram = self.parent.vram if self.parent is not None else self.vram
+        paddingStart = self.vram + paddingOffset - sectionVram
+        paddingEnd = self.vramEnd - sectionVram
+        for alignment in (8, 16):
+            if (paddingStart + alignment - 1) & ~(alignment - 1) == paddingEnd:
+                return paddingOffset, alignment
+        return None
+
     def countDiffOpcodes(self, other: SymbolFunction) -> int:
         result = 0
         for i in range(min(self.nInstr, other.nInstr)):
@@ -313,6 +575,9 @@ class SymbolFunction(SymbolText):
         return f"%lo({symName})"
 
     def getImmOverrideForInstruction(self, instr: rabbitizer.Instruction, instructionOffset: int) -> str|None:
+        if instructionOffset in self.rejectedReferences:
+            return None
+
         if len(self.context.relocSymbols[self.sectionType]) > 0:
             # Check possible symbols using reloc information (probably from a .o elf file)
             possibleImmOverride = self.context.getRelocSymbol(self.inFileOffset + instructionOffset, self.sectionType)
@@ -338,7 +603,7 @@ class SymbolFunction(SymbolText):
             if not self.pointersRemoved and instructionOffset in self.instrAnalyzer.symbolInstrOffset:
                 address = self.instrAnalyzer.symbolInstrOffset[instructionOffset]
 
-                if address in self.context.bannedSymbols:
+                if self.context.isAddressBanned(address):
                     return None
 
                 instrVram = self.getVramOffset(instructionOffset)
@@ -386,6 +651,85 @@ class SymbolFunction(SymbolText):
 
         return None
 
+    def disassembleInstruction(self, instr: rabbitizer.Instruction, immOverride: str|None, extraLJust: int=0) -> str:
+        if instr.isNop():
+            if not rabbitizer.config.pseudos_enablePseudos or not InstructionConfig.PSEUDO_NOP:
+                # rabbitizer always disassembles a zero word as `nop`, so build the `sll` following its formatting settings instead
+                useNamedRegisters = rabbitizer.config.regNames_namedRegisters and rabbitizer.config.regNames_gprAbiNames != rabbitizer.Abi.NUMERIC
+                zeroRegister = "$zero" if useNamedRegisters else "$0"
+                return f"{'sll'.ljust(rabbitizer.config.misc_opcodeLJust + extraLJust)} {zeroRegister}, {zeroRegister}, 0"
+
+        if common.GlobalConfig.ASM_SLL_HINTS_AS_MNEMONICS:
+            hintMnemonic = self.SllHintsMnemonics.get(instr.getRaw(), None)
+            if hintMnemonic is not None:
+                return hintMnemonic
+
+        return instr.disassemble(immOverride, extraLJust=extraLJust)
+
+    def getPseudoLa(self, instr: rabbitizer.Instruction, instructionOffset: int, luiLine: str, hiOverride: str) -> str|None:
+        """Returns the `la` pseudo-instruction replacing the `lui` at `instructionOffset` and the `addiu` right after it, or `None` if those can't be replaced.
+
+        The assembler expands `la` back to the same pair of instructions only if both are adjacent, use the same register and reference a symbol"""
+        if not common.GlobalConfig.ASM_PSEUDO_LA:
+            return None
+        if not instr.canBeHi() or self.instrAnalyzer.hiToLowDict.get(instructionOffset) != instructionOffset + 4:
+            return None
+
+        loOffset = instructionOffset + 4
+        if loOffset in self.instrAnalyzer.constantInstrOffset or loOffset not in self.instrAnalyzer.symbolInstrOffset:
+            return None
+        loInstr = self.instructions[loOffset//4]
+        if loInstr.uniqueId != rabbitizer.InstrId.cpu_addiu:
+            return None
+        if loInstr.rs != instr.rt or loInstr.rt != instr.rt:
+            return None
+        if self.getLabelForOffset(loOffset) != "":
+            # Something branches to the addiu
+            return None
+
+        loOverride = self.getImmOverrideForInstruction(loInstr, loOffset)
+        if not hiOverride.startswith("%hi(") or loOverride is None or not loOverride.startswith("%lo("):
+            return None
+        symName = hiOverride[len("%hi("):-1]
+        if symName != loOverride[len("%lo("):-1]:
+            return None
+
+        # Keep the alignment of the operands, `la` is one character shorter than `lui`
+        return "la " + luiLine[len("lui"):].replace(hiOverride, symName)
+
+    @staticmethod
+    def isStackPointerAdjustment(instr: rabbitizer.Instruction) -> bool:
+        "Checks if the instruction is an `addiu $sp, $sp, N`, or a `daddiu $sp, $sp, N` if `GlobalConfig.ABI` has 64-bit registers"
+        if instr.uniqueId != rabbitizer.InstrId.cpu_addiu and not (instr.uniqueId == rabbitizer.InstrId.cpu_daddiu and common.GlobalConfig.ABI.hasGpr64Bits):
+            return False
+        return instr.rs in {rabbitizer.RegGprO32.sp, rabbitizer.RegGprN32.sp} and instr.rt in {rabbitizer.RegGprO32.sp, rabbitizer.RegGprN32.sp}
+
+    def checkStackFrame(self) -> StackFrameWarning|None:
+        """Checks the first `addiu $sp, $sp, -N` of this function is matched by an `addiu $sp, $sp, N` on every epilogue.
+
+        Returns `None` if the stack frame is balanced or if the function doesn't touch the stack pointer"""
+        if self.isEmittedAsData():
+            return None
+
+        allocatedSize: int|None = None
+        releasedSizes: list[int] = list()
+        for instr in self.instructions:
+            if not self.isStackPointerAdjustment(instr):
+                continue
+
+            imm = instr.getImmediate()
+            if imm & 0x8000:
+                if allocatedSize is None:
+                    allocatedSize = 0x10000 - imm
+            else:
+                releasedSizes.append(imm)
+
+        if allocatedSize is None and len(releasedSizes) == 0:
+            return None
+        if allocatedSize is not None and len(releasedSizes) > 0 and all(size == allocatedSize for size in releasedSizes):
+            return None
+        return StackFrameWarning(self.getName(), self.vram, allocatedSize, releasedSizes)
+
     def getLabelForOffset(self, instructionOffset: int) -> str:
         if common.GlobalConfig.IGNORE_BRANCHES or instructionOffset == 0:
             # Skip over this function to avoid duplication
@@ -403,24 +747,156 @@ class SymbolFunction(SymbolText):
 
         labelSym.isDefined = True
         labelSym.sectionType = self.sectionType
-        if labelSym.type == common.SymbolSpecialType.function or labelSym.type == common.SymbolSpecialType.jumptablelabel:
-            label = labelSym.getSymbolLabel() + common.GlobalConfig.LINE_ENDS
+        if labelSym.type in {common.SymbolSpecialType.function, common.SymbolSpecialType.jumptablelabel, common.SymbolSpecialType.altentry}:
+            label = ""
+            if common.GlobalConfig.ASM_COMMENT_JUMP_TABLE_CASES and len(labelSym.jumpTableCases) > 0:
+                casesStr = ", ".join(f"{jumpTableSym.getName()} case {caseIndex}" for jumpTableSym, caseIndex in labelSym.jumpTableCases)
+                label += f"# Reached from {casesStr}" + common.GlobalConfig.LINE_ENDS
+            label += labelSym.getSymbolLabel() + common.GlobalConfig.LINE_ENDS
             if common.GlobalConfig.ASM_TEXT_FUNC_AS_LABEL:
                 label += f"{labelSym.getName()}:{common.GlobalConfig.LINE_ENDS}"
             return label
         return labelSym.getName() + ":" + common.GlobalConfig.LINE_ENDS
 
 
+    def _getGprValueBefore(self, instructionOffset: int, register: int, ignoreLabelAtStart: bool=False) -> int|None:
+        """Returns the value `register` holds right before the instruction at `instructionOffset` if it is a constant built with `lui`, `ori` and `addiu`
+
+        `ignoreLabelAtStart` allows a label at `instructionOffset`, for when only the path coming from the previous instruction matters"""
+        if register == 0:
+            return 0
+
+        offset = instructionOffset
+        while offset > 0:
+            if not (ignoreLabelAtStart and offset == instructionOffset) and self.getSymbol(self.getVramOffset(offset), tryPlusOffset=False) is not None:
+                # Other paths may reach this instruction with another value
+                return None
+            offset -= 4
+
+            instr = self.instructions[offset//4]
+            if not (instr.modifiesRt() and instr.rt.value == register) and not (instr.modifiesRd() and instr.rd.value == register):
+                continue
+
+            if instr.uniqueId == rabbitizer.InstrId.cpu_lui:
+                return instr.getImmediate() << 16
+            if instr.uniqueId == rabbitizer.InstrId.cpu_ori:
+                value = self._getGprValueBefore(offset, instr.rs.value)
+                return None if value is None else value | instr.getImmediate()
+            if instr.uniqueId == rabbitizer.InstrId.cpu_addiu:
+                value = self._getGprValueBefore(offset, instr.rs.value)
+                signedImm = rabbitizer.Utils.from2Complement(instr.getImmediate(), 16)
+                return None if value is None else (value + signedImm) & 0xFFFFFFFF
+            return None
+        return None
+
+    def _getMtc1Operands(self, instr: rabbitizer.Instruction) -> tuple[int, int]|None:
+        "Returns the GPR and the float register of a `mtc1` instruction"
+        if instr.uniqueId != rabbitizer.InstrId.cpu_mtc1:
+            return None
+        return instr.rt.value, instr.fs.value
+
+    def getFloatConstantComment(self, instr: rabbitizer.Instruction, instructionOffset: int) -> str|None:
+        "Returns the decoded value of the float constant moved to a float register by this `mtc1`, or `None` if it isn't a known constant"
+        operands = self._getMtc1Operands(instr)
+        if operands is None:
+            return None
+        gpr, fpr = operands
+        value = self._getGprValueBefore(instructionOffset, gpr)
+        if value is None:
+            return None
+
+        # Look for the other half of a double near this instruction
+        for otherOffset in range(instructionOffset - 16, instructionOffset + 20, 4):
+            if otherOffset == instructionOffset or otherOffset < 0 or otherOffset >= len(self.instructions) * 4:
+                continue
+            otherOperands = self._getMtc1Operands(self.instructions[otherOffset//4])
+            if otherOperands is None or otherOperands[1] != fpr ^ 1:
+                continue
+            if otherOffset > instructionOffset:
+                # Only the second instruction of the pair is annotated
+                return None
+            otherValue = self._getGprValueBefore(otherOffset, otherOperands[0])
+            if otherValue is None:
+                return None
+            # The odd register holds the upper half of the double
+            hi, lo = (value, otherValue) if fpr % 2 == 1 else (otherValue, value)
+            return f"{common.Utils.qwordToDouble((hi << 32) | lo)}"
+
+        return f"{common.Utils.wordToFloat(value)}f"
+
+    def getTrapCodeComment(self, instr: rabbitizer.Instruction) -> str|None:
+        "Returns the comment the user gave to the code of this `break` or `syscall` instruction, if any"
+        # The code is read from the raw instruction, since `break` and `syscall` split it differently
+        if instr.uniqueId == rabbitizer.InstrId.cpu_break:
+            # Only the upper 10 bits, the ones set by `break code`
+            return self.context.breakCodesComments.get((instr.getRaw() >> 16) & 0x3FF)
+        if instr.uniqueId == rabbitizer.InstrId.cpu_syscall:
+            return self.context.syscallCodesComments.get((instr.getRaw() >> 6) & 0xFFFFF)
+        return None
+
+    def getCacheOpComment(self, instr: rabbitizer.Instruction) -> str|None:
+        "Returns the name of the operation and the cache targeted by this `cache` instruction"
+        if instr.uniqueId != rabbitizer.InstrId.cpu_cache:
+            return None
+        # The operation is encoded on the rt field
+        op = instr.rt.value
+        cache = op & 0x3
+        instrName, dataName = self.CacheOperationNames[op >> 2]
+        # Even caches are instruction caches, odd ones are data caches
+        return f"{dataName if cache & 1 else instrName} ({self.CacheNames[cache]})"
+
+    def getInstructionAnnotation(self, instr: rabbitizer.Instruction, instructionOffset: int) -> str:
+        "Returns the extra comment for this instruction requested by `GlobalConfig.ASM_COMMENT_SYMBOL_REFERENCES`, `GlobalConfig.ASM_COMMENT_FRAME_SIZE`, `GlobalConfig.ASM_COMMENT_FLOAT_CONSTANTS`, `GlobalConfig.ASM_COMMENT_CACHE_OPS` and the trap codes comments of the context, or an empty string"
+        if not common.GlobalConfig.ASM_COMMENT:
+            return ""
+
+        annotations: list[str] = []
+
+        if common.GlobalConfig.ASM_COMMENT_SYMBOL_REFERENCES:
+            # Only the instruction completing the address (the lo one) is annotated
+            if instructionOffset in self.instrAnalyzer.symbolInstrOffset and not instr.canBeHi():
+                address = self.instrAnalyzer.symbolInstrOffset[instructionOffset]
+                symbol = self.getSymbol(address, tryPlusOffset=True)
+                if symbol is not None:
+                    annotations.append(f"-> {symbol.getSymbolPlusOffset(address)}")
+                else:
+                    annotations.append(f"-> 0x{address:08X}")
+
+        if common.GlobalConfig.ASM_COMMENT_FRAME_SIZE:
+            if self.isStackPointerAdjustment(instr) and instr.getImmediate() & 0x8000:
+                frameSize = 0x10000 - instr.getImmediate()
+                annotations.append(f"frame 0x{frameSize:X}")
+
+        if common.GlobalConfig.ASM_COMMENT_FLOAT_CONSTANTS:
+            floatComment = self.getFloatConstantComment(instr, instructionOffset)
+            if floatComment is not None:
+                annotations.append(floatComment)
+
+        if common.GlobalConfig.ASM_COMMENT_CACHE_OPS:
+            cacheComment = self.getCacheOpComment(instr)
+            if cacheComment is not None:
+                annotations.append(cacheComment)
+
+        trapComment = self.getTrapCodeComment(instr)
+        if trapComment is not None:
+            annotations.append(trapComment)
+
+        if len(annotations) == 0:
+            return ""
+        return " # " + ", ".join(annotations)
+
     def disassemble(self) -> str:
         output = ""
 
-        if not common.GlobalConfig.DISASSEMBLE_UNKNOWN_INSTRUCTIONS:
-            if self.hasUnimplementedIntrs:
-                return self.disassembleAsData()
+        if self.isEmittedAsData():
+            return self.disassembleAsData()
 
         if self.isLikelyHandwritten:
             output += "# Handwritten function" + common.GlobalConfig.LINE_ENDS
 
+        if self.isUnterminated:
+            output += "# Warning: this function does not end before the section does" + common.GlobalConfig.LINE_ENDS
+
         output += self.getLabel()
 
         if common.GlobalConfig.ASM_TEXT_ENT_LABEL:
@@ -429,18 +905,50 @@ class SymbolFunction(SymbolText):
         if common.GlobalConfig.ASM_TEXT_FUNC_AS_LABEL:
             output += f"{self.getName()}:" + common.GlobalConfig.LINE_ENDS
 
+        trailingPadding = self.getTrailingPadding() if common.GlobalConfig.ASM_TEXT_PADDING_AS_BALIGN else None
+
+        usesAtRegister = False
+        instructionsStart = len(output)
+
         wasLastInstABranch = False
         instructionOffset = 0
+        skipNextInstr = False
         for instr in self.instructions:
+            if trailingPadding is not None and instructionOffset == trailingPadding[0]:
+                paddingSize = len(self.instructions) * 4 - instructionOffset
+                output += f"# Padding of 0x{paddingSize:X} bytes" + common.GlobalConfig.LINE_ENDS
+                output += f".balign {trailingPadding[1]}" + common.GlobalConfig.LINE_ENDS
+                break
+
+            if skipNextInstr:
+                # Already emitted as part of a pseudo-instruction
+                skipNextInstr = False
+                wasLastInstABranch = instr.hasDelaySlot()
+                instructionOffset += 4
+                continue
+
             immOverride = self.getImmOverrideForInstruction(instr, instructionOffset)
             comment = self.generateAsmLineComment(instructionOffset, instr.getRaw())
             extraLJust = 0
 
             if wasLastInstABranch:
-                extraLJust = -1
-                comment += " "
+                delaySlotIndent = common.GlobalConfig.ASM_TEXT_DELAY_SLOT_INDENT
+                extraLJust = -len(delaySlotIndent)
+                comment += delaySlotIndent
 
-            line = instr.disassemble(immOverride, extraLJust=extraLJust)
+            line = self.disassembleInstruction(instr, immOverride, extraLJust=extraLJust)
+
+            if not wasLastInstABranch and immOverride is not None:
+                laLine = self.getPseudoLa(instr, instructionOffset, line, immOverride)
+                if laLine is not None:
+                    line = laLine
+                    instr = self.instructions[instructionOffset//4 + 1]
+                    skipNextInstr = True
+
+            if self.AtRegisterPattern.search(line) is not None:
+                usesAtRegister = True
+
+            line += self.getInstructionAnnotation(instr, instructionOffset + (4 if skipNextInstr else 0))
 
             label = self.getLabelForOffset(instructionOffset)
             output += f"{label}{comment}  {line}" + common.GlobalConfig.LINE_ENDS
@@ -448,9 +956,43 @@ class SymbolFunction(SymbolText):
             wasLastInstABranch = instr.hasDelaySlot()
             instructionOffset += 4
 
+        if common.GlobalConfig.ASM_TEXT_SET_NOAT and usesAtRegister:
+            output = output[:instructionsStart] + ".set noat" + common.GlobalConfig.LINE_ENDS + output[instructionsStart:]
+            output += ".set at" + common.GlobalConfig.LINE_ENDS
+
         if common.GlobalConfig.ASM_TEXT_END_LABEL:
             output += f"{common.GlobalConfig.ASM_TEXT_END_LABEL} {self.getName()}" + common.GlobalConfig.LINE_ENDS
 
+        output += self.getSizeDirective()
+        output += self.getReferencesFooter()
+        return output
+
+    def _getReferencesFooterList(self, title: str, names: list[str]) -> str:
+        output = f"# {title}:" + common.GlobalConfig.LINE_ENDS
+        maxEntries = common.GlobalConfig.ASM_COMMENT_FUNCTION_REFERENCES_MAX
+        for name in names[:maxEntries]:
+            output += f"#     {name}" + common.GlobalConfig.LINE_ENDS
+        if len(names) > maxEntries:
+            output += f"#     ... ({len(names) - maxEntries} more)" + common.GlobalConfig.LINE_ENDS
+        return output
+
+    def getReferencesFooter(self) -> str:
+        "Comment listing the functions calling or referencing this one and the symbols referenced by this function, derived from the relocations of each function"
+        if not common.GlobalConfig.ASM_COMMENT_FUNCTION_REFERENCES:
+            return ""
+
+        callers = [funcSym.getName() for funcSym in self.contextSym.referenceFunctions]
+
+        references: list[str] = []
+        for reloc in self.getRelocations():
+            if reloc.relocType == RelocTypes.R_MIPS_26:
+                continue
+            if reloc.symbol not in references:
+                references.append(reloc.symbol)
+
+        output = common.GlobalConfig.LINE_ENDS
+        output += self._getReferencesFooterList("Referenced by", callers)
+        output += self._getReferencesFooterList("References", references)
         return output
 
     def disassembleAsData(self) -> str:
@@ -7,6 +7,8 @@ from __future__ import annotations
 
 from ... import common
 
+from ..MipsRelocInfo import RelocInfo
+from ..MipsRelocTypes import RelocTypes
 from . import SymbolBase
 
 
@@ -46,13 +48,46 @@ class SymbolRodata(SymbolBase):
         return self.contextSym.isJumpTable()
 
 
+    @staticmethod
+    def resolveGpRelEntry(w: int) -> int|None:
+        "Returns the address a gp relative jump table entry points to, or `None` if `GlobalConfig.GP_VALUE` isn't set"
+        gpValue = common.GlobalConfig.GP_VALUE
+        if gpValue is None:
+            return None
+        if w & 0x80000000:
+            w -= 0x100000000
+        return (gpValue + w) & 0xFFFFFFFF
+
+    def isGpRelJumpTable(self) -> bool:
+        return self.isJumpTable() and self.contextSym.isGpRelJumpTable
+
+
+    def isSharedString(self) -> bool:
+        "Checks if this is a string referenced by enough different functions to be kept in the shared rodata, see `GlobalConfig.STRING_SHARED_REFERENCES_THRESHOLD`"
+        threshold = common.GlobalConfig.STRING_SHARED_REFERENCES_THRESHOLD
+        if threshold <= 0 or not self.isString():
+            return False
+        return len(self.contextSym.referenceFunctions) >= threshold
+
+    def isMigratable(self) -> bool:
+        """Checks if this symbol can be migrated to the function which uses it.
+
+        Strings are decided by the amount of different functions referencing them (see `GlobalConfig.STRING_SHARED_REFERENCES_THRESHOLD`), so a string used many times by a single function can still be migrated.
+        Any other symbol, or every symbol if the threshold is disabled, must be referenced exactly once"""
+        if common.GlobalConfig.STRING_SHARED_REFERENCES_THRESHOLD > 0 and self.isString():
+            return len(self.contextSym.referenceFunctions) > 0 and not self.isSharedString()
+        return self.contextSym.referenceCounter == 1
+
     def isRdata(self) -> bool:
         "Checks if the current symbol is .rdata"
+        if self.contextSym.migrateToFunction is not None:
+            # Explicitly migrated to a function
+            return False
         if self.contextSym.isMaybeConstVariable():
             return True
 
         # This symbol could be an unreferenced non-const variable
-        if self.contextSym.referenceCounter == 1:
+        if self.isMigratable():
             # This const variable was already used in a function
             return False
 
@@ -79,11 +114,13 @@ class SymbolRodata(SymbolBase):
             if self.sizew % 2 != 0:
                 # doubles require an even amount of words
                 self.contextSym.type = None
+                self.contextSym.isMaybeDouble = False
             else:
                 for i in range(self.sizew // 2):
                     if not self.isDouble(i*2):
                         # checks there's no other overlaping symbols
                         self.contextSym.type = None
+                        self.contextSym.isMaybeDouble = False
                         break
 
         super().analyze()
@@ -111,8 +148,37 @@ class SymbolRodata(SymbolBase):
         return count
 
 
+    def getRelocations(self, canReferenceSymbolsWithAddends: bool|None=None) -> list[RelocInfo]:
+        if self.isGpRelJumpTable():
+            relocs: list[RelocInfo] = list()
+            for i, w in enumerate(self.words):
+                target = self.resolveGpRelEntry(w)
+                if target is None:
+                    break
+                labelSym = self.getSymbol(target, tryPlusOffset=False)
+                if labelSym is not None:
+                    relocs.append(RelocInfo(self.inFileOffset + 4*i, RelocTypes.R_MIPS_GPREL32, labelSym.getName()))
+            return relocs
+
+        # Rodata words never reference symbols with addends
+        return super().getRelocations(canReferenceSymbolsWithAddends=False)
+
+    def getIntToFloatComment(self, i: int, dotType: str) -> str:
+        "Shows the value used by the code for integers which get converted to floats"
+        if i != 0 or dotType != ".word" or not self.contextSym.isIntConvertedToFloat:
+            return ""
+        w = self.words[i]
+        if w & 0x80000000:
+            w -= 0x100000000
+        return f" # {float(w)} when converted to float"
+
+    def getJumpTableCaseComment(self, i: int) -> str:
+        if not common.GlobalConfig.ASM_COMMENT_JUMP_TABLE_CASES or not self.isJumpTable():
+            return ""
+        return f" # case {i}"
+
     def getNthWord(self, i: int, canReferenceSymbolsWithAddends: bool=False, canReferenceConstants: bool=False) -> tuple[str, int]:
-        if self.contextSym.isByte() or self.contextSym.isShort():
+        if self.contextSym.isByte() or self.contextSym.isShort() or self.contextSym.structLayout is not None:
             return super().getNthWord(i, canReferenceSymbolsWithAddends, canReferenceConstants)
 
         localOffset = 4*i
@@ -138,33 +204,36 @@ class SymbolRodata(SymbolBase):
         dotType = ".word"
         skip = 0
 
-        if self.isFloat(i):
+        # Values which wouldn't assemble back to the same bits are emitted as raw words instead
+        floatStr = common.Utils.wordToFloatStr(w) if self.isFloat(i) else None
+        doubleStr = common.Utils.qwordToDoubleStr((w << 32) | self.words[i+1]) if floatStr is None and self.isDouble(i) else None
+
+        if floatStr is not None:
             dotType = ".float"
-            value = str(common.Utils.wordToFloat(w))
-        elif self.isDouble(i):
+            value = floatStr
+        elif doubleStr is not None:
             dotType = ".double"
-            otherHalf = self.words[i+1]
-            doubleWord = (w << 32) | otherHalf
-            value = str(common.Utils.qwordToDouble(doubleWord))
-            rodataWord = doubleWord
+            value = doubleStr
+            rodataWord = (w << 32) | self.words[i+1]
             skip = 1
         else:
-            labelSym = self.getSymbol(w, tryPlusOffset=False)
+            gpRelTarget = self.resolveGpRelEntry(w) if self.isGpRelJumpTable() else None
+            if gpRelTarget is not None:
+                labelSym = self.getSymbol(gpRelTarget, tryPlusOffset=False)
+                if labelSym is not None:
+                    # The assembler computes `label - _gp`, which gives back the original value
+                    dotType = ".gpword"
+            else:
+                labelSym = self.getSymbol(w, tryPlusOffset=False)
+                if labelSym is not None and not self.context.shouldCreateReference(self.getVramOffset(localOffset), w, RelocTypes.R_MIPS_32.name):
+                    labelSym = None
             if labelSym is not None:
                 value = labelSym.getName()
             elif self.isString():
-                try:
-                    buffer = bytearray(4*len(self.words))
-                    common.Utils.beWordsToBytes(self.words, buffer)
-                    decodedValue, rawStringSize = common.Utils.decodeString(buffer, 4*i)
-                    dotType = ".asciz"
-                    value = f'"{decodedValue}"'
-                    value += common.GlobalConfig.LINE_ENDS + (22 * " ") + ".balign 4"
+                stringWord = self.getNthWordAsString(i)
+                if stringWord is not None:
+                    dotType, value, skip = stringWord
                     rodataWord = None
-                    skip = rawStringSize // 4
-                except (UnicodeDecodeError, RuntimeError):
-                    # Not a string
-                    pass
 
         comment = self.generateAsmLineComment(localOffset, rodataWord)
-        return f"{label}{comment} {dotType} {value}" + common.GlobalConfig.LINE_ENDS, skip
+        return f"{label}{comment} {dotType} {value}{self.getArrayIndexComment(localOffset)}{self.getIntToFloatComment(i, dotType)}{self.getJumpTableCaseComment(i)}" + common.GlobalConfig.LINE_ENDS, skip
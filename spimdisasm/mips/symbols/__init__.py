@@ -14,4 +14,4 @@ from .MipsSymbolData import SymbolData
 from .MipsSymbolRodata import SymbolRodata
 from .MipsSymbolBss import SymbolBss
 
-from .MipsSymbolFunction import SymbolFunction
+from .MipsSymbolFunction import SymbolFunction, StackFrameWarning
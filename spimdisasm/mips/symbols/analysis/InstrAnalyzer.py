@@ -11,8 +11,10 @@ from .... import common
 
 
 class InstrAnalyzer:
-    def __init__(self, funcVram: int) -> None:
+    def __init__(self, funcVram: int, funcVramEnd: int|None=None) -> None:
         self.funcVram = funcVram
+        self.funcVramEnd = funcVramEnd
+        "Used to tell apart the `j`s to labels of this same function from the ones to other functions"
 
         self.referencedVrams: set[int] = set()
         "Every referenced vram found"
@@ -39,6 +41,14 @@ class InstrAnalyzer:
         # Jump register (jumptables)
         self.jumpRegisterIntrOffset: dict[int, int] = dict()
         self.referencedJumpTableOffsets: dict[int, int] = dict()
+        self.gprLoadedFromJumpTable: dict[int, tuple[int, int]] = dict()
+        "key: number of the gpr register, value: the address of the table it was loaded from with a `%lo` `lw` and the offset of that `lw`"
+        self.gprScaledIndexes: set[int] = set()
+        "Registers holding an index multiplied by 4 with `sll $r, $index, 2`"
+        self.gprIndexedBases: set[int] = set()
+        "Registers holding a base plus a scaled index, set by `addu $r, $base, $index`"
+        self.indexedLoadOffsets: set[int] = set()
+        "Offsets of the `lw` instructions which use an indexed base register"
 
         # Constants
         self.constantHiInstrOffset: dict[int, int] = dict()
@@ -60,6 +70,23 @@ class InstrAnalyzer:
 
         self.possibleSymbolTypes: dict[int, str] = dict()
 
+        self.symbolAccessTypes: dict[int, set[str]] = dict()
+        "key: symbol address, value: every type inferred from the instructions accessing that address"
+
+        # int to float conversions
+        self.gprLoadedFromSymbol: dict[int, int] = dict()
+        "key: number of the gpr register, value: address of the symbol its value was loaded from with a `lw`"
+        self.fprLoadedFromSymbol: dict[int, int] = dict()
+        "key: number of the fpr register, value: address of the symbol its value was moved from with a `mtc1`"
+        self.intToFloatSymbols: set[int] = set()
+        "Addresses of symbols whose value gets loaded as an integer and then converted to a float"
+
+        # Indirect function calls
+        self.gprLoadedAddresses: dict[int, tuple[int, int]] = dict()
+        "key: number of the gpr register, value: the address loaded into it by a `lui`/`addiu` pair and the offset of the `addiu`"
+        self.indirectFuncCallInstrOffsets: dict[int, int] = dict()
+        "key: `jalr` instruction offset, value: target vram, which was loaded into the register shortly before the call"
+
         # %hi/%lo pairing
         self.hiToLowDict: dict[int, int] = dict()
         "key: %hi instruction offset, value: %lo instruction offset"
@@ -228,7 +255,25 @@ class InstrAnalyzer:
 
         return address
 
+    Cop1MovesIds: set[rabbitizer.Enum] = {
+        rabbitizer.InstrId.cpu_cfc1,
+        rabbitizer.InstrId.cpu_ctc1,
+        rabbitizer.InstrId.cpu_mfc1,
+        rabbitizer.InstrId.cpu_mtc1,
+        rabbitizer.InstrId.cpu_dmfc1,
+        rabbitizer.InstrId.cpu_dmtc1,
+    }
+    "Moves between gprs and the COP1 (and its control registers), which never access memory"
+
     def processSymbolType(self, address: int, instr: rabbitizer.Instruction) -> None:
+        # Moves to or from the COP1 don't tell anything about the type of the symbol, treating them as float accesses would mislabel integer data as floats
+        if instr.uniqueId in self.Cop1MovesIds:
+            return
+
+        # Both `lw $t, %lo(sym)($at)` and the loads through a register holding the address of the symbol get here
+        if instr.uniqueId == rabbitizer.InstrId.cpu_lw:
+            self.gprLoadedFromSymbol[instr.rt.value] = address
+
         instrType = instr.mapInstrToType()
         if instrType is None:
             return
@@ -236,6 +281,10 @@ class InstrAnalyzer:
         if address not in self.possibleSymbolTypes:
             self.possibleSymbolTypes[address] = instrType
 
+        if address not in self.symbolAccessTypes:
+            self.symbolAccessTypes[address] = set()
+        self.symbolAccessTypes[address].add(instrType)
+
     def processSymbolDereferenceType(self, regsTracker: rabbitizer.RegistersTracker, instr: rabbitizer.Instruction, instrOffset: int) -> None:
         address = regsTracker.getAddressIfCanSetType(instr, instrOffset)
         if address is None:
@@ -243,6 +292,41 @@ class InstrAnalyzer:
 
         self.processSymbolType(address, instr)
 
+    def processIntToFloatConversion(self, instr: rabbitizer.Instruction) -> None:
+        # Registers are tracked by their number, since the gpr and fpr numbers are the only thing that matters here
+        if instr.uniqueId == rabbitizer.InstrId.cpu_mtc1:
+            address = self.gprLoadedFromSymbol.get(instr.rt.value, None)
+            if address is not None:
+                self.fprLoadedFromSymbol[instr.fs.value] = address
+            else:
+                self.fprLoadedFromSymbol.pop(instr.fs.value, None)
+        elif instr.uniqueId in {rabbitizer.InstrId.cpu_cvt_s_w, rabbitizer.InstrId.cpu_cvt_d_w}:
+            address = self.fprLoadedFromSymbol.get(instr.fs.value, None)
+            if address is not None:
+                self.intToFloatSymbols.add(address)
+        elif instr.modifiesRt():
+            self.gprLoadedFromSymbol.pop(instr.rt.value, None)
+        elif instr.modifiesRd():
+            self.gprLoadedFromSymbol.pop(instr.rd.value, None)
+
+
+    IndirectCallWindow: int = 8
+    "How many instructions may be placed between the `addiu` which loads an address and the `jalr` which uses it"
+
+    def processIndirectCall(self, instr: rabbitizer.Instruction, instrOffset: int) -> None:
+        if instr.uniqueId == rabbitizer.InstrId.cpu_jalr:
+            loadedAddress = self.gprLoadedAddresses.get(instr.rs.value, None)
+            if loadedAddress is not None:
+                address, loadOffset = loadedAddress
+                if (instrOffset - loadOffset) // 4 <= self.IndirectCallWindow:
+                    self.indirectFuncCallInstrOffsets[instrOffset] = address
+
+        # Forget the address as soon as the register gets overwritten
+        if instr.modifiesRt():
+            self.gprLoadedAddresses.pop(instr.rt.value, None)
+        elif instr.modifiesRd():
+            self.gprLoadedAddresses.pop(instr.rd.value, None)
+
 
     def symbolFinder(self, regsTracker: rabbitizer.RegistersTracker, instr: rabbitizer.Instruction, prevInstr: rabbitizer.Instruction|None, instrOffset: int) -> None:
         if instr.canBeHi():
@@ -292,10 +376,46 @@ class InstrAnalyzer:
         address = self.processSymbol(address, luiOffset, instr, instrOffset)
         if address is not None:
             regsTracker.processLo(instr, address, instrOffset)
+            if instr.uniqueId == rabbitizer.InstrId.cpu_addiu and luiOffset is not None:
+                self.gprLoadedAddresses[instr.rt.value] = (address, instrOffset)
+            elif instr.uniqueId == rabbitizer.InstrId.cpu_lw and luiOffset is not None and instrOffset in self.indexedLoadOffsets:
+                self.gprLoadedFromJumpTable[instr.rt.value] = (address, instrOffset)
+
+
+    def processJumpTableLoad(self, instr: rabbitizer.Instruction, instrOffset: int) -> None:
+        # Follows the `sll $t, $index, 2; addu $at, $at, $t; lw $t, %lo(table)($at)` idiom.
+        # The `lw` itself is registered later by `symbolFinder`, only if its base is an indexed one
+        if instr.uniqueId == rabbitizer.InstrId.cpu_lw and instr.rs.value in self.gprIndexedBases:
+            self.indexedLoadOffsets.add(instrOffset)
+
+        modifiedReg: int|None = None
+        if instr.modifiesRt():
+            modifiedReg = instr.rt.value
+        elif instr.modifiesRd():
+            modifiedReg = instr.rd.value
+        if modifiedReg is None:
+            return
 
+        self.gprLoadedFromJumpTable.pop(modifiedReg, None)
+        isScaledIndex = instr.uniqueId == rabbitizer.InstrId.cpu_sll and instr.sa == 2
+        isIndexedBase = instr.uniqueId == rabbitizer.InstrId.cpu_addu and (instr.rs.value in self.gprScaledIndexes or instr.rt.value in self.gprScaledIndexes)
+        self.gprScaledIndexes.discard(modifiedReg)
+        self.gprIndexedBases.discard(modifiedReg)
+        if isScaledIndex:
+            self.gprScaledIndexes.add(modifiedReg)
+        elif isIndexedBase:
+            self.gprIndexedBases.add(modifiedReg)
 
     def processJumpRegister(self, regsTracker: rabbitizer.RegistersTracker, instr: rabbitizer.Instruction, instrOffset: int) -> None:
         jrInfo = regsTracker.getJrInfo(instr)
+        if jrInfo is None:
+            # The register tracker may not follow the `addu` of the usual switch idiom, so use the loads from an indexed base:
+            # `sll $t, $index, 2; lui $at, %hi(table); addu $at, $at, $t; lw $t, %lo(table)($at); jr $t`
+            jrInfo = self.gprLoadedFromJumpTable.get(instr.rs.value, None)
+            if jrInfo is not None:
+                address, offset = jrInfo
+                jrInfo = (offset, address)
+
         if jrInfo is not None:
             offset, address = jrInfo
 
@@ -305,9 +425,17 @@ class InstrAnalyzer:
 
 
     def processInstr(self, regsTracker: rabbitizer.RegistersTracker, instr: rabbitizer.Instruction, instrOffset: int, currentVram: int, prevInstr: rabbitizer.Instruction|None=None) -> None:
+        self.processIntToFloatConversion(instr)
+        self.processIndirectCall(instr, instrOffset)
+        self.processJumpTableLoad(instr, instrOffset)
+
         if instr.isBranch() or instr.isUnconditionalBranch():
             self.processBranch(instr, instrOffset, currentVram)
 
+        elif instr.uniqueId == rabbitizer.InstrId.cpu_j and self.funcVramEnd is not None and self.funcVram <= instr.getInstrIndexAsVram() < self.funcVramEnd:
+            # A `j` to a label of this same function is just a branch with a bigger range
+            self.processBranch(instr, instrOffset, currentVram)
+
         elif instr.isJType():
             self.processFuncCall(instr, instrOffset)
 
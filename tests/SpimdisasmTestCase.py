@@ -0,0 +1,85 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import contextlib
+import io
+from typing import Any, Iterator
+import unittest
+
+from spimdisasm import common
+from spimdisasm import mips
+
+
+def wordsToBytes(words: list[int]) -> bytearray:
+    "Packs the words as big endian"
+    result = bytearray()
+    for word in words:
+        result += word.to_bytes(4, "big")
+    return result
+
+def padToWord(data: bytes) -> bytearray:
+    return bytearray(data) + bytearray(-len(data) % 4)
+
+
+class SpimdisasmTestCase(unittest.TestCase):
+    """Base class of every test.
+
+    `GlobalConfig` is restored after each test, so tests can freely change it"""
+
+    def setUp(self) -> None:
+        self.savedGlobalConfig: dict[str, Any] = {key: value for key, value in vars(common.GlobalConfig).items() if key.isupper()}
+        common.GlobalConfig.QUIET = True
+
+    def tearDown(self) -> None:
+        for key, value in self.savedGlobalConfig.items():
+            setattr(common.GlobalConfig, key, value)
+
+
+    def createContext(self) -> common.Context:
+        "A context whose global segment covers the rom and vram used by the tests"
+        context = common.Context()
+        context.globalSegment.changeRanges(0x0, 0x100000, 0x80000000, 0x80100000)
+        return context
+
+    def createText(self, context: common.Context, words: list[int], vram: int=0x80000000, vromStart: int=0x0) -> mips.sections.SectionText:
+        return self._createAt(mips.sections.SectionText, context, wordsToBytes(words), vram, vromStart)
+
+    def createRodata(self, context: common.Context, data: bytes, vram: int=0x80001000, vromStart: int=0x0) -> mips.sections.SectionRodata:
+        return self._createAt(mips.sections.SectionRodata, context, padToWord(data), vram, vromStart)
+
+    def createData(self, context: common.Context, data: bytes, vram: int=0x80002000, vromStart: int=0x0) -> mips.sections.SectionData:
+        return self._createAt(mips.sections.SectionData, context, padToWord(data), vram, vromStart)
+
+    @staticmethod
+    def _createAt(sectionClass: Any, context: common.Context, data: bytearray, vram: int, vromStart: int) -> Any:
+        "Places `data` at `vromStart` of a zero filled rom, so the vrom of the symbols matches the one of a real rom"
+        array_of_bytes = bytearray(vromStart) + data
+        return sectionClass(context, vromStart, len(array_of_bytes), vram, "test", array_of_bytes, 0, None)
+
+    @staticmethod
+    def getDirectives(output: str) -> list[str]:
+        """Returns the instructions and directives of a disassembly, without the offset comments and with the extra whitespace collapsed.
+
+        Labels and blank lines are skipped"""
+        directives: list[str] = list()
+        for line in output.splitlines():
+            hasOffsetComment = "*/" in line
+            line = " ".join(line.split("*/", 1)[-1].split())
+            if hasOffsetComment or (line.startswith(".") and not line.endswith(":")):
+                directives.append(line)
+        return directives
+
+    @contextlib.contextmanager
+    def captureWarnings(self) -> Iterator[io.StringIO]:
+        "Collects everything printed to stderr by the disassembler, including the warnings silenced by `GlobalConfig.QUIET`"
+        common.GlobalConfig.QUIET = False
+        stderr = io.StringIO()
+        try:
+            with contextlib.redirect_stderr(stderr):
+                yield stderr
+        finally:
+            common.GlobalConfig.QUIET = True
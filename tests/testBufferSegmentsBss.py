@@ -0,0 +1,71 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from spimdisasm import common
+from spimdisasm import mips
+
+from SpimdisasmTestCase import SpimdisasmTestCase
+
+
+class TestBufferSegmentsBss(SpimdisasmTestCase):
+    "Two buffer segments which share the same rom and the same bss addresses"
+
+    bssVramStart = 0x80400000
+    bssVramEnd = 0x80400010
+
+    def createBufferContext(self) -> common.Context:
+        context = self.createContext()
+        context.addOverlaySegment("buffer1", 0x1000, 0x2000, 0x80100000, 0x80101000)
+        context.addOverlaySegment("buffer2", 0x1000, 0x2000, 0x80200000, 0x80201000)
+        return context
+
+    def createBss(self, context: common.Context, overlayCategory: str) -> mips.sections.SectionBss:
+        return mips.sections.SectionBss(context, 0x2000, 0x2000, self.bssVramStart, self.bssVramEnd, "bss", 0x1000, overlayCategory)
+
+    def test_independentSymbols(self) -> None:
+        context = self.createBufferContext()
+        buffer1 = context.overlaySegments["buffer1"][0x1000]
+        buffer2 = context.overlaySegments["buffer2"][0x1000]
+        buffer1.addSymbol(0x80400008)
+
+        bss1 = self.createBss(context, "buffer1")
+        bss2 = self.createBss(context, "buffer2")
+        bss1.analyze()
+        bss2.analyze()
+
+        symbols1 = list(buffer1.getSymbolsInRange(self.bssVramStart, self.bssVramEnd))
+        symbols2 = list(buffer2.getSymbolsInRange(self.bssVramStart, self.bssVramEnd))
+        self.assertEqual([(sym.vram, sym.autodetectedSize) for sym in symbols1], [(0x80400000, 0x8), (0x80400008, 0x8)])
+        self.assertEqual([(sym.vram, sym.autodetectedSize) for sym in symbols2], [(0x80400000, 0x10)])
+        self.assertIsNot(symbols1[0], symbols2[0])
+
+        self.assertEqual(list(context.globalSegment.getSymbolsInRange(self.bssVramStart, self.bssVramEnd)), [])
+        self.assertEqual(list(context.unknownSegment.getSymbolsInRange(self.bssVramStart, self.bssVramEnd)), [])
+
+    def test_declaredRangesUnchanged(self) -> None:
+        context = self.createBufferContext()
+        buffer1 = context.overlaySegments["buffer1"][0x1000]
+
+        bss1 = self.createBss(context, "buffer1")
+        self.assertFalse(buffer1.isVramInRange(self.bssVramStart))
+
+        bss1.analyze()
+        self.assertTrue(buffer1.isVramInRange(self.bssVramStart))
+        self.assertFalse(buffer1.isVramInRange(self.bssVramEnd))
+        self.assertEqual((buffer1.vramStart, buffer1.vramEnd), (0x80100000, 0x80101000))
+
+    def test_sameRomInSameCategory(self) -> None:
+        context = self.createBufferContext()
+
+        with self.assertRaises(RuntimeError):
+            context.addOverlaySegment("buffer1", 0x1000, 0x2000, 0x80300000, 0x80301000)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,76 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from SpimdisasmTestCase import SpimdisasmTestCase
+
+
+class TestCodeDataPartition(SpimdisasmTestCase):
+    "A text section with a data island between two functions"
+
+    words = [
+        # func_80000000
+        0x24020001, # addiu $v0, $zero, 1
+        0x03E00008, # jr $ra
+        0x00000000, # nop
+        # data island
+        0x12345678,
+        0x9ABCDEF0,
+        # func_80000014
+        0x03E00008, # jr $ra
+        0x00000000, # nop
+    ]
+
+    def test_partition(self) -> None:
+        context = self.createContext()
+        text = self.createText(context, self.words)
+        text.addDataHole(0x8000000C, 8)
+        text.analyze()
+
+        self.assertEqual(text.getCodeDataRanges(), [
+            (0x80000000, 0x8000000C, True),
+            (0x8000000C, 0x80000014, False),
+            (0x80000014, 0x8000001C, True),
+        ])
+        self.assertEqual(text.getFunctionRanges(), [(0x80000000, 0x8000000C), (0x80000014, 0x8000001C)])
+
+        output = text.disassemble()
+        self.assertIn(".word 0x12345678", output)
+        self.assertIn(".word 0x9ABCDEF0", output)
+        self.assertIn("glabel func_80000014", output)
+
+    def test_withoutDataIsland(self) -> None:
+        context = self.createContext()
+        text = self.createText(context, self.words[:3])
+        text.analyze()
+
+        self.assertEqual(text.getCodeDataRanges(), [(0x80000000, 0x8000000C, True)])
+
+    def test_adjacentFunctionsAreMerged(self) -> None:
+        context = self.createContext()
+        text = self.createText(context, self.words[:3] + self.words[5:])
+        text.analyze()
+
+        self.assertEqual(text.getFunctionRanges(), [(0x80000000, 0x8000000C), (0x8000000C, 0x80000014)])
+        self.assertEqual(text.getCodeDataRanges(), [(0x80000000, 0x80000014, True)])
+
+    def test_invalidDataHoles(self) -> None:
+        context = self.createContext()
+        text = self.createText(context, self.words)
+        text.addDataHole(0x8000000C, 8)
+
+        with self.assertRaises(RuntimeError):
+            text.addDataHole(0x80000010, 4)
+        with self.assertRaises(RuntimeError):
+            text.addDataHole(0x8000000E, 4)
+        with self.assertRaises(RuntimeError):
+            text.addDataHole(0x80000018, 8)
+
+
+if __name__ == "__main__":
+    unittest.main()
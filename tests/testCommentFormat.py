@@ -0,0 +1,43 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import argparse
+import unittest
+
+from spimdisasm import common
+
+from SpimdisasmTestCase import SpimdisasmTestCase, wordsToBytes
+
+
+class TestCommentFormat(SpimdisasmTestCase):
+    "The `--asm-comment-format` template is validated when the arguments are parsed"
+
+    def parseArgs(self, commentFormat: str) -> None:
+        parser = argparse.ArgumentParser()
+        common.GlobalConfig.addParametersToArgParse(parser)
+        common.GlobalConfig.parseArgs(parser.parse_args(["--asm-comment-format", commentFormat]))
+
+    def test_validFormat(self) -> None:
+        self.parseArgs("# {vram} {rom}")
+
+        context = self.createContext()
+        rodata = self.createRodata(context, wordsToBytes([0x00000001]))
+        rodata.analyze()
+        self.assertIn("# 80001000 000000 .word 0x00000001", rodata.disassemble())
+
+    def test_unknownField(self) -> None:
+        with self.assertRaises(RuntimeError):
+            self.parseArgs("/* {vram} {size} */")
+        self.assertEqual(common.GlobalConfig.ASM_COMMENT_FORMAT, self.savedGlobalConfig["ASM_COMMENT_FORMAT"])
+
+    def test_unbalancedBraces(self) -> None:
+        with self.assertRaises(RuntimeError):
+            self.parseArgs("/* {vram */")
+
+
+if __name__ == "__main__":
+    unittest.main()
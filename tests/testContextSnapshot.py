@@ -0,0 +1,61 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from spimdisasm import common
+
+from SpimdisasmTestCase import SpimdisasmTestCase
+
+
+class TestContextSnapshot(SpimdisasmTestCase):
+    "Symbols modified through a snapshot must not affect the context until committed"
+
+    def createSymbols(self) -> tuple[common.Context, common.ContextSymbol]:
+        context = self.createContext()
+        funcSym = context.globalSegment.addFunction(0x80000000)
+        jumpTableSym = context.globalSegment.addJumpTable(0x80001000)
+        labelSym = context.globalSegment.addSymbol(0x80000010)
+        labelSym.setStructLayout([(0, "s16"), (4, "s32")])
+        labelSym.accessTypes.add("s32")
+        labelSym.addReferenceFunction(funcSym)
+        labelSym.addJumpTableCase(jumpTableSym, 0)
+        return context, labelSym
+
+    def modify(self, contextSym: common.ContextSymbol, context: common.Context) -> None:
+        assert contextSym.structLayout is not None
+        contextSym.structLayout.append((8, "s8"))
+        contextSym.accessTypes.add("f32")
+        contextSym.addReferenceFunction(context.globalSegment.addFunction(0x80000100))
+        contextSym.addJumpTableCase(context.globalSegment.addJumpTable(0x80001000), 1)
+
+    def test_discard(self) -> None:
+        context, labelSym = self.createSymbols()
+        snapshot = common.ContextSnapshot(context)
+        self.modify(snapshot.addSymbol(0x80000010), context)
+        snapshot.discard()
+
+        self.assertEqual(labelSym.structLayout, [(0, "s16"), (4, "s32")])
+        self.assertEqual(labelSym.accessTypes, {"s32"})
+        self.assertEqual([sym.vram for sym in labelSym.referenceFunctions], [0x80000000])
+        self.assertEqual([(sym.vram, index) for sym, index in labelSym.jumpTableCases], [(0x80001000, 0)])
+
+    def test_commit(self) -> None:
+        context, labelSym = self.createSymbols()
+        snapshot = common.ContextSnapshot(context)
+        self.modify(snapshot.addSymbol(0x80000010), context)
+        snapshot.commit()
+
+        self.assertIs(context.globalSegment.getSymbol(0x80000010), labelSym)
+        self.assertEqual(labelSym.structLayout, [(0, "s16"), (4, "s32"), (8, "s8")])
+        self.assertEqual(labelSym.accessTypes, {"s32", "f32"})
+        self.assertEqual([sym.vram for sym in labelSym.referenceFunctions], [0x80000000, 0x80000100])
+        self.assertEqual([(sym.vram, index) for sym, index in labelSym.jumpTableCases], [(0x80001000, 0), (0x80001000, 1)])
+
+
+if __name__ == "__main__":
+    unittest.main()
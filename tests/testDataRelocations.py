@@ -0,0 +1,59 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from spimdisasm import mips
+
+from SpimdisasmTestCase import SpimdisasmTestCase, wordsToBytes
+
+
+class TestDataRelocations(SpimdisasmTestCase):
+    "Data words which are absolute addresses of symbols need `R_MIPS_32` relocations"
+
+    textWords = [
+        0x03E00008, # jr $ra
+        0x00000000, # nop
+    ]
+    dataWords = [
+        0x80000000, # pointer to func_80000000
+        0x00000001,
+        0x80003000, # pointer to D_80003000
+        0x00000002,
+        0x00000003,
+        0x00000000,
+    ]
+
+    def test_dataPointers(self) -> None:
+        context = self.createContext()
+        text = self.createText(context, self.textWords)
+        context.globalSegment.addSymbol(0x80003000)
+        data = self.createData(context, wordsToBytes(self.dataWords), vram=0x80002000, vromStart=0x2000)
+        text.analyze()
+        data.analyze()
+
+        output = data.disassemble()
+        self.assertIn(".word func_80000000", output)
+        self.assertIn(".word D_80003000", output)
+
+        relocs = data.getRelocations()
+        self.assertEqual([(reloc.offset, reloc.relocType, reloc.symbol) for reloc in relocs], [
+            (0x0, mips.RelocTypes.R_MIPS_32, "func_80000000"),
+            (0x8, mips.RelocTypes.R_MIPS_32, "D_80003000"),
+        ])
+        self.assertEqual(relocs[0].computeValue(0x80000000), 0x80000000)
+
+    def test_plainWordsAreNotRelocated(self) -> None:
+        context = self.createContext()
+        data = self.createData(context, wordsToBytes([0x00000001, 0x00000002]), vram=0x80002000, vromStart=0x2000)
+        data.analyze()
+
+        self.assertEqual(data.getRelocations(), [])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,84 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import io
+import os
+import subprocess
+import sys
+import unittest
+
+from SpimdisasmTestCase import SpimdisasmTestCase, wordsToBytes
+
+
+class TestDeterministicOutput(SpimdisasmTestCase):
+    "Disassembling the same input always produces byte identical output, regardless of the hash seed used by Python"
+
+    textWords = [
+        # func_80000000
+        0x3C048000, # lui $a0, %hi(STR_80001000)
+        0x0C000007, # jal func_8000001C
+        0x24841000, # addiu $a0, $a0, %lo(STR_80001000)
+        0x3C018000, # lui $at, %hi(D_80001008)
+        0xC4201008, # lwc1 $f0, %lo(D_80001008)($at)
+        0x03E00008, # jr $ra
+        0x00000000, # nop
+        # func_8000001C
+        0x03E00008, # jr $ra
+        0x00000000, # nop
+    ]
+    rodataBytes = b"hello\0\0\0" + wordsToBytes([0x3F800000, 0x00000000])
+    dataWords = [
+        0x8000001C,
+        0x80001000,
+        0x80001008,
+        0x00000000,
+    ]
+
+    def disassembleSample(self) -> str:
+        "Disassembles every section of the sample and appends the exported symbols"
+        context = self.createContext()
+        text = self.createText(context, self.textWords)
+        rodata = self.createRodata(context, self.rodataBytes, vram=0x80001000, vromStart=0x1000)
+        data = self.createData(context, wordsToBytes(self.dataWords), vram=0x80002000, vromStart=0x2000)
+        text.analyze()
+        rodata.analyze()
+        data.analyze()
+
+        output = text.disassemble() + rodata.disassemble() + data.disassemble()
+        symbolsJson = io.StringIO()
+        context.exportSymbolsJson(symbolsJson)
+        return output + symbolsJson.getvalue()
+
+    def disassembleInSubprocess(self, hashSeed: str) -> str:
+        env = dict(os.environ)
+        env["PYTHONHASHSEED"] = hashSeed
+        repoPath = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+        env["PYTHONPATH"] = os.pathsep.join(path for path in (repoPath, env.get("PYTHONPATH")) if path)
+
+        script = "; ".join([
+            "import sys",
+            "from testDeterministicOutput import TestDeterministicOutput",
+            "test = TestDeterministicOutput()",
+            "test.setUp()",
+            "sys.stdout.write(test.disassembleSample())",
+        ])
+        result = subprocess.run([sys.executable, "-c", script], cwd=os.path.dirname(os.path.abspath(__file__)), env=env, stdout=subprocess.PIPE, check=True)
+        return result.stdout.decode("utf-8")
+
+    def test_sameProcess(self) -> None:
+        self.assertEqual(self.disassembleSample(), self.disassembleSample())
+
+    def test_differentHashSeeds(self) -> None:
+        expected = self.disassembleSample()
+        self.assertIn("glabel func_8000001C", expected)
+
+        for hashSeed in ("0", "1", "12345"):
+            self.assertEqual(self.disassembleInSubprocess(hashSeed), expected)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,58 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import struct
+import unittest
+
+from spimdisasm import common
+
+from SpimdisasmTestCase import SpimdisasmTestCase, wordsToBytes
+
+
+class TestDoubleGuesser(SpimdisasmTestCase):
+    "8 bytes rodata symbols which aren't accessed by any instruction can be guessed as doubles"
+
+    rodataWords = [
+        0x400921FB, 0x54442D18, # 3.141592653589793
+        0x7FF80000, 0x00000000, # NaN
+    ]
+
+    def disassembleRodata(self, userType: str|None=None) -> list[str]:
+        context = self.createContext()
+        firstSym = context.globalSegment.addSymbol(0x80001000)
+        firstSym.type = userType
+        context.globalSegment.addSymbol(0x80001008)
+        rodata = self.createRodata(context, wordsToBytes(self.rodataWords))
+        rodata.analyze()
+
+        return self.getDirectives(rodata.disassemble())
+
+    def test_guessedDouble(self) -> None:
+        common.GlobalConfig.DOUBLE_GUESSER = True
+        directives = self.disassembleRodata()
+
+        self.assertEqual(directives[0].split()[0], ".double")
+        # The emitted value must assemble back to the same bytes
+        value = float(directives[0].split()[1])
+        self.assertEqual(struct.pack(">d", value), wordsToBytes(self.rodataWords[0:2]))
+
+        # NaN is not a reasonable value for a double
+        self.assertEqual(directives[1:], [".word 0x7FF80000", ".word 0x00000000"])
+
+    def test_guesserDisabled(self) -> None:
+        common.GlobalConfig.DOUBLE_GUESSER = False
+
+        self.assertEqual(self.disassembleRodata()[:2], [".word 0x400921FB", ".word 0x54442D18"])
+
+    def test_userTypeOverridesGuess(self) -> None:
+        common.GlobalConfig.DOUBLE_GUESSER = True
+
+        self.assertEqual(self.disassembleRodata(userType="s32")[:2], [".word 0x400921FB", ".word 0x54442D18"])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,68 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from spimdisasm import common
+from spimdisasm import mips
+
+from SpimdisasmTestCase import SpimdisasmTestCase, wordsToBytes
+
+
+class TestGpRelJumpTables(SpimdisasmTestCase):
+    "Jump tables whose entries are offsets relative to `GlobalConfig.GP_VALUE` are emitted with `.gpword`"
+
+    rodataWords = [
+        0xFFFF8018, # L80000018 - _gp
+        0xFFFF8024, # L80000024 - _gp
+        # Points to _gp itself, outside of the function using the table
+        0x00000000,
+    ]
+
+    def analyzeRodata(self) -> tuple[common.Context, mips.sections.SectionRodata]:
+        context = self.createContext()
+        jumpTableSym = context.globalSegment.addJumpTable(0x80001000)
+        jumpTableSym.jumpTableTargetsRange = (0x80000000, 0x80000030)
+        rodata = self.createRodata(context, wordsToBytes(self.rodataWords))
+        rodata.analyze()
+        return context, rodata
+
+    def test_gpword(self) -> None:
+        common.GlobalConfig.GP_VALUE = 0x80008000
+        context, rodata = self.analyzeRodata()
+
+        jumpTableSym = context.globalSegment.getSymbol(0x80001000, tryPlusOffset=False)
+        assert jumpTableSym is not None
+        self.assertTrue(jumpTableSym.isGpRelJumpTable)
+
+        labels = [sym.vram for sym in context.globalSegment.symbols.values() if sym.type == common.SymbolSpecialType.jumptablelabel]
+        self.assertEqual(labels, [0x80000018, 0x80000024])
+
+        self.assertEqual(self.getDirectives(rodata.disassemble()), [
+            ".gpword L80000018",
+            ".gpword L80000024",
+            ".word 0x00000000",
+        ])
+
+        relocs = [(reloc.offset, reloc.relocType, reloc.symbol) for reloc in rodata.symbolList[0].getRelocations()]
+        self.assertEqual(relocs, [
+            (0x0, mips.RelocTypes.R_MIPS_GPREL32, "L80000018"),
+            (0x4, mips.RelocTypes.R_MIPS_GPREL32, "L80000024"),
+        ])
+
+    def test_withoutGp(self) -> None:
+        common.GlobalConfig.GP_VALUE = None
+        context, rodata = self.analyzeRodata()
+
+        jumpTableSym = context.globalSegment.getSymbol(0x80001000, tryPlusOffset=False)
+        assert jumpTableSym is not None
+        self.assertFalse(jumpTableSym.isGpRelJumpTable)
+        self.assertNotIn(".gpword", rodata.disassemble())
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,57 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from spimdisasm import common
+
+from SpimdisasmTestCase import SpimdisasmTestCase
+
+
+class TestIndirectCalls(SpimdisasmTestCase):
+    "Addresses loaded with `lui`/`addiu` into the register used by a `jalr` are functions"
+
+    def analyzeCall(self, clobber: list[int]) -> tuple[common.Context, list[str]]:
+        words = [
+            # func_80000000
+            0x3C198000, # lui $t9, %hi(0x80000100)
+            0x27390100, # addiu $t9, $t9, %lo(0x80000100)
+            *clobber,
+            0x0320F809, # jalr $t9
+            0x00000000, # nop
+            0x03E00008, # jr $ra
+            0x00000000, # nop
+        ]
+        context = self.createContext()
+        text = self.createText(context, words)
+        text.analyze()
+        return context, self.getDirectives(text.disassemble())
+
+    def test_call(self) -> None:
+        context, directives = self.analyzeCall([])
+
+        funcSym = context.globalSegment.getSymbol(0x80000100, tryPlusOffset=False)
+        assert funcSym is not None
+        self.assertEqual(funcSym.type, common.SymbolSpecialType.function)
+        self.assertEqual(funcSym.getName(), "func_80000100")
+        self.assertEqual(directives[:3], [
+            "lui $t9, %hi(func_80000100)",
+            "addiu $t9, $t9, %lo(func_80000100)",
+            "jalr $t9",
+        ])
+
+    def test_clobberedRegister(self) -> None:
+        context, _ = self.analyzeCall([
+            0x0000C825, # move $t9, $zero
+        ])
+
+        funcSym = context.globalSegment.getSymbol(0x80000100, tryPlusOffset=False)
+        self.assertTrue(funcSym is None or funcSym.type != common.SymbolSpecialType.function)
+
+
+if __name__ == "__main__":
+    unittest.main()
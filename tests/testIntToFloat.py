@@ -0,0 +1,59 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from SpimdisasmTestCase import SpimdisasmTestCase, wordsToBytes
+
+
+class TestIntToFloat(SpimdisasmTestCase):
+    "Rodata loaded with `lwc1` is a float, while rodata loaded with `lw` and converted with `mtc1`/`cvt.s.w` is an integer"
+
+    textWords = [
+        # func_80000000
+        0x3C018000, # lui $at, %hi(D_80001000)
+        0xC4201000, # lwc1 $f0, %lo(D_80001000)($at)
+        0x03E00008, # jr $ra
+        0x00000000, # nop
+        # func_80000010
+        0x3C018000, # lui $at, %hi(D_80001004)
+        0x8C2E1004, # lw $t6, %lo(D_80001004)($at)
+        0x448E2000, # mtc1 $t6, $f4
+        0x468021A0, # cvt.s.w $f6, $f4
+        0x03E00008, # jr $ra
+        0x00000000, # nop
+    ]
+    rodataWords = [
+        0x42C80000, # 100.0f
+        0x00000064, # 100
+    ]
+
+    def disassembleRodata(self, textWords: list[int]) -> list[str]:
+        "Returns the data directives of the rodata"
+        context = self.createContext()
+        text = self.createText(context, textWords)
+        rodata = self.createRodata(context, wordsToBytes(self.rodataWords), vram=0x80001000, vromStart=0x1000)
+        text.analyze()
+        rodata.analyze()
+        return self.getDirectives(rodata.disassemble())
+
+    def test_floatAndConvertedInt(self) -> None:
+        self.assertEqual(self.disassembleRodata(self.textWords), [
+            ".float 100.0",
+            ".word 0x00000064 # 100.0 when converted to float",
+        ])
+
+    def test_intWithoutConversion(self) -> None:
+        # Replace the `cvt.s.w` with a `nop`, so the value is moved to the COP1 but never converted
+        textWords = list(self.textWords)
+        textWords[7] = 0x00000000
+
+        self.assertEqual(self.disassembleRodata(textWords)[1], ".word 0x00000064")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,93 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from spimdisasm import common
+
+
+class TestIntervalMap(unittest.TestCase):
+    "Adjacency and exact boundary cases of `IntervalMap`"
+
+    def createMap(self, mergeOverlapping: bool=False) -> common.IntervalMap[str]:
+        intervalMap: common.IntervalMap[str] = common.IntervalMap(mergeOverlapping)
+        intervalMap.insertRange(0x10, 0x20, "a")
+        intervalMap.insertRange(0x30, 0x40, "b")
+        return intervalMap
+
+    def test_exactBoundaries(self) -> None:
+        intervalMap = self.createMap()
+
+        self.assertIsNone(intervalMap.get(0x0F))
+        self.assertEqual(intervalMap.get(0x10), "a")
+        self.assertEqual(intervalMap.get(0x1F), "a")
+        self.assertIsNone(intervalMap.get(0x20))
+        self.assertIsNone(intervalMap.get(0x2F))
+        self.assertEqual(intervalMap.getRange(0x30), (0x30, 0x40, "b"))
+        self.assertIsNone(intervalMap.getRange(0x40))
+
+        self.assertIn(0x10, intervalMap)
+        self.assertNotIn(0x20, intervalMap)
+        self.assertNotIn("0x10", intervalMap)
+
+    def test_overlappingQueries(self) -> None:
+        intervalMap = self.createMap()
+
+        self.assertEqual(list(intervalMap.overlapping(0x00, 0x10)), [])
+        self.assertEqual(list(intervalMap.overlapping(0x20, 0x30)), [])
+        self.assertEqual(list(intervalMap.overlapping(0x1F, 0x20)), [(0x10, 0x20, "a")])
+        self.assertEqual(list(intervalMap.overlapping(0x18, 0x31)), [(0x10, 0x20, "a"), (0x30, 0x40, "b")])
+        self.assertEqual(list(intervalMap.overlapping(0x00, 0x50)), [(0x10, 0x20, "a"), (0x30, 0x40, "b")])
+        self.assertEqual(list(intervalMap.overlapping(0x12, 0x14)), [(0x10, 0x20, "a")])
+
+    def test_adjacentInserts(self) -> None:
+        for mergeOverlapping in (False, True):
+            intervalMap = self.createMap(mergeOverlapping)
+            intervalMap.insertRange(0x20, 0x30, "c")
+            intervalMap.insertRange(0x08, 0x10, "d")
+            intervalMap.insertRange(0x40, 0x48, "e")
+
+            self.assertEqual(list(intervalMap), [
+                (0x08, 0x10, "d"),
+                (0x10, 0x20, "a"),
+                (0x20, 0x30, "c"),
+                (0x30, 0x40, "b"),
+                (0x40, 0x48, "e"),
+            ])
+            self.assertEqual(len(intervalMap), 5)
+
+    def test_overlappingInsertErrors(self) -> None:
+        intervalMap = self.createMap()
+
+        for start, end in ((0x10, 0x20), (0x1F, 0x21), (0x2F, 0x31), (0x00, 0x11), (0x00, 0x50), (0x12, 0x14)):
+            with self.assertRaises(RuntimeError):
+                intervalMap.insertRange(start, end, "c")
+        self.assertEqual(list(intervalMap), [(0x10, 0x20, "a"), (0x30, 0x40, "b")])
+
+    def test_overlappingInsertMerges(self) -> None:
+        intervalMap = self.createMap(mergeOverlapping=True)
+        intervalMap.insertRange(0x1F, 0x31, "c")
+        self.assertEqual(list(intervalMap), [(0x10, 0x40, "c")])
+
+        intervalMap.insertRange(0x12, 0x14, "d")
+        self.assertEqual(list(intervalMap), [(0x10, 0x40, "d")])
+
+        intervalMap.insertRange(0x40, 0x50, "e")
+        self.assertEqual(list(intervalMap), [(0x10, 0x40, "d"), (0x40, 0x50, "e")])
+
+    def test_invalidRanges(self) -> None:
+        for mergeOverlapping in (False, True):
+            intervalMap: common.IntervalMap[str] = common.IntervalMap(mergeOverlapping)
+            with self.assertRaises(RuntimeError):
+                intervalMap.insertRange(0x10, 0x10, "a")
+            with self.assertRaises(RuntimeError):
+                intervalMap.insertRange(0x20, 0x10, "a")
+            self.assertEqual(len(intervalMap), 0)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,67 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from spimdisasm import common
+
+from SpimdisasmTestCase import SpimdisasmTestCase, wordsToBytes
+
+
+class TestJumpTables(SpimdisasmTestCase):
+    "Jump tables used by the usual `switch` idiom"
+
+    textWords = [
+        # func_80000000
+        0x00047080, # sll $t6, $a0, 2
+        0x3C018000, # lui $at, %hi(jtbl_80001000)
+        0x002E0821, # addu $at, $at, $t6
+        0x8C2E1000, # lw $t6, %lo(jtbl_80001000)($at)
+        0x01C00008, # jr $t6
+        0x00000000, # nop
+        # case 0
+        0x24020001, # addiu $v0, $zero, 1
+        0x10000002, # b .L80000028
+        0x00000000, # nop
+        # case 1
+        0x24020002, # addiu $v0, $zero, 2
+        0x03E00008, # jr $ra
+        0x00000000, # nop
+    ]
+
+    rodataWords = [
+        0x80000018,
+        0x80000024,
+        # Outside of the function using the table, so it isn't part of the table
+        0x80000100,
+    ]
+
+    def test_switch(self) -> None:
+        context = self.createContext()
+        text = self.createText(context, self.textWords)
+        text.analyze()
+        rodata = self.createRodata(context, wordsToBytes(self.rodataWords))
+        rodata.analyze()
+
+        jumpTableSym = context.globalSegment.getSymbol(0x80001000, tryPlusOffset=False)
+        assert jumpTableSym is not None
+        self.assertEqual(jumpTableSym.type, common.SymbolSpecialType.jumptable)
+        self.assertEqual(jumpTableSym.jumpTableTargetsRange, (0x80000000, 0x80000030))
+
+        labels = [sym.vram for sym in context.globalSegment.symbols.values() if sym.type == common.SymbolSpecialType.jumptablelabel]
+        self.assertEqual(labels, [0x80000018, 0x80000024])
+
+        self.assertEqual(self.getDirectives(rodata.disassemble()), [
+            ".word L80000018",
+            ".word L80000024",
+            ".word 0x80000100",
+        ])
+        self.assertIn("lw $t6, %lo(jtbl_80001000)($at)", self.getDirectives(text.disassemble()))
+
+
+if __name__ == "__main__":
+    unittest.main()
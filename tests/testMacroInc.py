@@ -0,0 +1,86 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from spimdisasm import common
+from spimdisasm import mips
+
+from SpimdisasmTestCase import SpimdisasmTestCase, wordsToBytes
+
+
+class TestMacroInc(SpimdisasmTestCase):
+    "The macro header generated for the configured label macros"
+
+    def test_defaultLabels(self) -> None:
+        self.assertEqual(mips.FilesHandlers.getMacroIncContents(), "\n".join([
+            ".macro glabel label",
+            "    .global \\label",
+            "    \\label:",
+            ".endm",
+            "",
+            "",
+        ]))
+
+    def test_customLabels(self) -> None:
+        common.GlobalConfig.ASM_DATA_LABEL = "dlabel"
+        common.GlobalConfig.ASM_TEXT_ENT_LABEL = ".ent"
+        common.GlobalConfig.ASM_TEXT_END_LABEL = "endlabel"
+
+        self.assertEqual(mips.FilesHandlers.getMacroIncContents(), "\n".join([
+            ".macro glabel label",
+            "    .global \\label",
+            "    .type \\label, @function",
+            "    \\label:",
+            ".endm",
+            "",
+            ".macro dlabel label",
+            "    .global \\label",
+            "    .type \\label, @object",
+            "    \\label:",
+            ".endm",
+            "",
+            ".macro endlabel label",
+            "    .end \\label",
+            ".endm",
+            "",
+            "",
+        ]))
+
+    def test_everyUsedMacroIsDefined(self) -> None:
+        common.GlobalConfig.ASM_TEXT_LABEL = "flabel"
+        common.GlobalConfig.ASM_DATA_LABEL = "dlabel"
+        common.GlobalConfig.ASM_TEXT_ENT_LABEL = "entlabel"
+        common.GlobalConfig.ASM_TEXT_END_LABEL = "endlabel"
+
+        context = self.createContext()
+        text = self.createText(context, [
+            0x03E00008, # jr $ra
+            0x00000000, # nop
+        ])
+        data = self.createData(context, wordsToBytes([0x80000000, 0x00000000]))
+        text.analyze()
+        data.analyze()
+
+        defined: set[str] = set()
+        for line in mips.FilesHandlers.getMacroIncContents().splitlines():
+            if line.startswith(".macro "):
+                defined.add(line.split()[1])
+
+        used: set[str] = set()
+        for line in (text.disassemble() + data.disassemble()).splitlines():
+            line = line.strip()
+            if line == "" or line.startswith("/*") or line.startswith(".") or line.endswith(":"):
+                continue
+            used.add(line.split()[0])
+
+        self.assertEqual(used, {"flabel", "dlabel", "entlabel", "endlabel"})
+        self.assertEqual(used, defined)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,63 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from spimdisasm import common
+
+from SpimdisasmTestCase import SpimdisasmTestCase
+
+
+class TestPseudoLa(SpimdisasmTestCase):
+    "Adjacent `lui`/`addiu` pairs loading the address of a symbol can be emitted as a `la` pseudo-instruction"
+
+    words = [
+        # func_80000000
+        0x3C048000, # lui $a0, %hi(D_80001000)
+        0x24841000, # addiu $a0, $a0, %lo(D_80001000)
+        0x3C058000, # lui $a1, %hi(D_80001010)
+        0x8CA51010, # lw $a1, %lo(D_80001010)($a1)
+        0x3C068000, # lui $a2, %hi(D_80001020)
+        0x00000000, # nop
+        0x24C61020, # addiu $a2, $a2, %lo(D_80001020)
+        0x03E00008, # jr $ra
+        0x00000000, # nop
+    ]
+
+    def disassembleText(self) -> list[str]:
+        context = self.createContext()
+        text = self.createText(context, self.words)
+        text.analyze()
+        return self.getDirectives(text.disassemble())
+
+    def test_pseudoLa(self) -> None:
+        common.GlobalConfig.ASM_PSEUDO_LA = True
+
+        self.assertEqual(self.disassembleText(), [
+            "la $a0, D_80001000",
+            # The low half is a load, so the pair is kept
+            "lui $a1, %hi(D_80001010)",
+            "lw $a1, %lo(D_80001010)($a1)",
+            # Not adjacent, so `la` wouldn't assemble back to the same instructions
+            "lui $a2, %hi(D_80001020)",
+            "nop",
+            "addiu $a2, $a2, %lo(D_80001020)",
+            "jr $ra",
+            "nop",
+        ])
+
+    def test_disabled(self) -> None:
+        common.GlobalConfig.ASM_PSEUDO_LA = False
+
+        self.assertEqual(self.disassembleText()[:2], [
+            "lui $a0, %hi(D_80001000)",
+            "addiu $a0, $a0, %lo(D_80001000)",
+        ])
+
+
+if __name__ == "__main__":
+    unittest.main()
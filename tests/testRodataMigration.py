@@ -0,0 +1,65 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import io
+import unittest
+
+from spimdisasm import mips
+
+from SpimdisasmTestCase import SpimdisasmTestCase, wordsToBytes
+
+
+class TestRodataMigration(SpimdisasmTestCase):
+    "Rodata symbols can be explicitly migrated to a function with `ContextSymbol.migrateToFunction`"
+
+    textWords = [
+        # func_80000000
+        0x03E00008, # jr $ra
+        0x00000000, # nop
+        # func_80000008
+        0x3C018000, # lui $at, %hi(0x80001000)
+        0x03E00008, # jr $ra
+        0xC4201000, # lwc1 $f0, %lo(0x80001000)($at)
+    ]
+
+    def analyze(self, migrateToFunction: int) -> tuple[list[mips.symbols.SymbolFunction], mips.sections.SectionRodata]:
+        context = self.createContext()
+        floatSym = context.globalSegment.addSymbol(0x80001000)
+        floatSym.type = "f32"
+        floatSym.migrateToFunction = migrateToFunction
+        text = self.createText(context, self.textWords)
+        text.analyze()
+        rodata = self.createRodata(context, wordsToBytes([0x3F800000]))
+        rodata.analyze()
+
+        funcs = [func for func in text.symbolList if isinstance(func, mips.symbols.SymbolFunction)]
+        self.assertEqual([func.vram for func in funcs], [0x80000000, 0x80000008])
+        return funcs, rodata
+
+    def test_migrateTo(self) -> None:
+        funcs, rodata = self.analyze(0x80000000)
+
+        # The heuristic would have migrated the float to the function which references it
+        pairings = mips.FilesHandlers.getFunctionsRodataPairing(funcs, [rodata])
+        self.assertEqual([pairing.migratedVrams for pairing in pairings], [[0x80001000], []])
+        self.assertFalse(rodata.symbolList[0].isRdata())
+
+        f = io.StringIO()
+        mips.FilesHandlers.writeSplittedFunctionToFile(f, funcs[0], [rodata])
+        self.assertIn(".float 1.0", self.getDirectives(f.getvalue()))
+
+    def test_migrateToNotAFunction(self) -> None:
+        funcs, rodata = self.analyze(0x80000004)
+
+        with self.assertRaises(RuntimeError):
+            mips.FilesHandlers.getFunctionsRodataPairing(funcs, [rodata])
+        with self.assertRaises(RuntimeError):
+            mips.FilesHandlers.getForcedRodataMigrations([rodata])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,76 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from spimdisasm import common
+from spimdisasm import mips
+
+from SpimdisasmTestCase import SpimdisasmTestCase
+
+
+class TestSharedStrings(SpimdisasmTestCase):
+    "Strings are migrated depending on the amount of different functions which reference them"
+
+    loadString = [
+        0x3C048000, # lui $a0, %hi(0x80001000)
+        0x24841000, # addiu $a0, $a0, %lo(0x80001000)
+    ]
+    funcEnd = [
+        0x03E00008, # jr $ra
+        0x00000000, # nop
+    ]
+
+    def getMigratedRodata(self, words: list[int]) -> dict[str, list[int]]:
+        "Returns the vrams of the rodata symbols migrated to each function"
+        context = self.createContext()
+        # The string guesser rejects symbols referenced more than once, so the type must be known
+        stringSym = context.globalSegment.addSymbol(0x80001000)
+        stringSym.type = "char"
+        text = self.createText(context, words)
+        text.analyze()
+        rodata = self.createRodata(context, b"hello\0")
+        rodata.analyze()
+        self.assertTrue(rodata.symbolList[0].isString())
+
+        migrated: dict[str, list[int]] = dict()
+        for func in text.symbolList:
+            assert isinstance(func, mips.symbols.SymbolFunction)
+            rdataList, lateRodataList, _ = mips.FilesHandlers.getRdataAndLateRodataForFunction(func, [rodata])
+            migrated[func.getName()] = [sym.vram for sym in rdataList + lateRodataList]
+        return migrated
+
+    def test_sharedByTwoFunctions(self) -> None:
+        common.GlobalConfig.STRING_SHARED_REFERENCES_THRESHOLD = 2
+
+        self.assertEqual(self.getMigratedRodata(self.loadString + self.funcEnd + self.loadString + self.funcEnd), {
+            "func_80000000": [],
+            "func_80000010": [],
+        })
+
+    def test_referencedTwiceByOneFunction(self) -> None:
+        common.GlobalConfig.STRING_SHARED_REFERENCES_THRESHOLD = 2
+
+        # Only a single function references the string, no matter how many times
+        self.assertEqual(self.getMigratedRodata(self.loadString + self.loadString + self.funcEnd), {
+            "func_80000000": [0x80001000],
+        })
+
+    def test_thresholdDisabled(self) -> None:
+        common.GlobalConfig.STRING_SHARED_REFERENCES_THRESHOLD = 0
+
+        # Strings follow the reference count rule of every other rodata symbol
+        self.assertEqual(self.getMigratedRodata(self.loadString + self.loadString + self.funcEnd), {
+            "func_80000000": [],
+        })
+        self.assertEqual(self.getMigratedRodata(self.loadString + self.funcEnd), {
+            "func_80000000": [0x80001000],
+        })
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,73 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from spimdisasm import common
+from spimdisasm import mips
+
+from SpimdisasmTestCase import SpimdisasmTestCase
+
+
+class TestSllHints(SpimdisasmTestCase):
+    "Rendering of the hints encoded as `sll $zero, $zero, N`"
+
+    words = [
+        0x00000040, # sll $zero, $zero, 1 (ssnop)
+        0x000000C0, # sll $zero, $zero, 3 (ehb)
+        0x00000140, # sll $zero, $zero, 5 (pause)
+        0x00000080, # sll $zero, $zero, 2 (not a hint)
+        0x00021080, # sll $v0, $v0, 2
+        0x03E00008, # jr $ra
+        0x00000000, # nop
+    ]
+
+    def disassembleInstructions(self) -> list[str]:
+        "Returns the instructions of the function, with the comments and the extra whitespace removed"
+        context = self.createContext()
+        text = self.createText(context, self.words)
+        text.analyze()
+
+        instructions = self.getDirectives(text.disassemble())
+        self.assertEqual(len(instructions), len(self.words))
+        return instructions
+
+    def test_rawSll(self) -> None:
+        instructions = self.disassembleInstructions()
+
+        self.assertEqual(instructions[:5], [
+            "sll $zero, $zero, 1",
+            "sll $zero, $zero, 3",
+            "sll $zero, $zero, 5",
+            "sll $zero, $zero, 2",
+            "sll $v0, $v0, 2",
+        ])
+
+    def test_hintMnemonics(self) -> None:
+        common.GlobalConfig.ASM_SLL_HINTS_AS_MNEMONICS = True
+        instructions = self.disassembleInstructions()
+
+        self.assertEqual(instructions, [
+            "ssnop",
+            "ehb",
+            "pause",
+            "sll $zero, $zero, 2",
+            "sll $v0, $v0, 2",
+            "jr $ra",
+            "nop",
+        ])
+
+    def test_rawNop(self) -> None:
+        self.addCleanup(setattr, mips.InstructionConfig, "PSEUDO_NOP", mips.InstructionConfig.PSEUDO_NOP)
+        mips.InstructionConfig.PSEUDO_NOP = False
+        instructions = self.disassembleInstructions()
+
+        self.assertEqual(instructions[-1], "sll $zero, $zero, 0")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,42 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import io
+import unittest
+
+from SpimdisasmTestCase import SpimdisasmTestCase
+
+
+class TestStringEncodingsReport(SpimdisasmTestCase):
+    "The report of the encoding used to decode each string symbol"
+
+    def test_overrideAndGlobal(self) -> None:
+        context = self.createContext()
+        sjisSym = context.globalSegment.addSymbol(0x80001000)
+        sjisSym.type = "char"
+        sjisSym.encoding = "SHIFT-JIS"
+        asciiSym = context.globalSegment.addSymbol(0x80001004)
+        asciiSym.type = "char"
+
+        rodata = self.createRodata(context, "あ".encode("SHIFT-JIS") + b"\0\0" + b"abc\0" + b"\x12\x34\x56\x78")
+        rodata.analyze()
+
+        output = rodata.disassemble()
+        self.assertIn('.asciz "あ"', output)
+        self.assertIn('.asciz "abc"', output)
+
+        report = io.StringIO()
+        context.saveStringEncodingsReport(report)
+        self.assertEqual(report.getvalue().splitlines(), [
+            "vram,name,encoding,source",
+            f"0x80001000,{sjisSym.getName()},SHIFT-JIS,override",
+            f"0x80001004,{asciiSym.getName()},EUC-JP,global",
+        ])
+
+
+if __name__ == "__main__":
+    unittest.main()
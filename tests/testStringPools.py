@@ -0,0 +1,51 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from spimdisasm import common
+
+from SpimdisasmTestCase import SpimdisasmTestCase
+
+
+class TestStringPools(SpimdisasmTestCase):
+    "A single rodata symbol containing many NUL separated strings"
+
+    def disassemblePool(self, data: bytes) -> list[str]:
+        "Returns the directives emitted for the pool at 0x80001000, without the comments"
+        context = self.createContext()
+        poolSym = context.globalSegment.addSymbol(0x80001000)
+        poolSym.type = "char"
+        rodata = self.createRodata(context, data)
+        rodata.analyze()
+
+        self.assertEqual([sym.vram for sym in context.getSymbolsInRange(0x80001000, 0x80001000 + len(data))], [0x80001000])
+        return self.getDirectives(rodata.disassemble())
+
+    def test_threeStrings(self) -> None:
+        common.GlobalConfig.ASM_STRING_POOLS = True
+
+        self.assertEqual(self.disassemblePool(b"ab\0cde\0fghij\0"), [
+            '.asciz "ab"',
+            '.asciz "cde"',
+            '.asciz "fghij"',
+            ".balign 4",
+        ])
+
+    def test_alignedStrings(self) -> None:
+        common.GlobalConfig.ASM_STRING_POOLS = True
+
+        self.assertEqual(self.disassemblePool(b"abc\0def\0ghi\0"), [
+            '.asciz "abc"',
+            '.asciz "def"',
+            '.asciz "ghi"',
+            ".balign 4",
+        ])
+
+
+if __name__ == "__main__":
+    unittest.main()
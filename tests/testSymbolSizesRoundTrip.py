@@ -0,0 +1,87 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import io
+import json
+import unittest
+
+from spimdisasm import common
+
+from SpimdisasmTestCase import SpimdisasmTestCase, wordsToBytes
+
+
+class TestSymbolSizesRoundTrip(SpimdisasmTestCase):
+    "Sizes inferred on a run are exported to the json symbol map and imported back as user sizes on the next one"
+
+    dataWords = [
+        0x80002008, # pointer to the rodata
+        0x00000001,
+        0x00000002,
+        0x00000003,
+        0x00000004,
+        0x00000000,
+    ]
+    rodataWords = [
+        0x3F800000, # 1.0f
+        0x40000000, # 2.0f
+        0x00000005,
+        0x00000006,
+    ]
+
+    def disassemble(self, context: common.Context) -> str:
+        data = self.createData(context, wordsToBytes(self.dataWords), vram=0x80001000, vromStart=0x1000)
+        rodata = self.createRodata(context, wordsToBytes(self.rodataWords), vram=0x80002000, vromStart=0x2000)
+        data.analyze()
+        rodata.analyze()
+        return data.disassemble() + rodata.disassemble()
+
+    def firstRun(self) -> tuple[common.Context, str]:
+        context = self.createContext()
+        floatSym = context.globalSegment.addSymbol(0x80002000)
+        floatSym.type = "f32"
+        floatSym.isUserDeclared = True
+        # An autogenerated symbol which is not referenced by anything
+        context.globalSegment.addSymbol(0x80001010)
+        return context, self.disassemble(context)
+
+    @staticmethod
+    def exportJson(context: common.Context) -> str:
+        buffer = io.StringIO()
+        context.exportSymbolsJson(buffer)
+        return buffer.getvalue()
+
+    def test_sizesAreExported(self) -> None:
+        context, _ = self.firstRun()
+
+        sizes = {entry["vram"]: entry["size"] for entry in json.loads(self.exportJson(context))["symbols"]}
+        self.assertEqual(sizes, {
+            0x80001000: 0x10,
+            0x80001010: 0x8,
+            0x80002000: 0x8,
+            0x80002008: 0x8,
+        })
+
+    def test_roundTrip(self) -> None:
+        firstContext, firstOutput = self.firstRun()
+        exported = self.exportJson(firstContext)
+
+        secondContext = self.createContext()
+        secondContext.importSymbolsJson(io.StringIO(exported))
+        for vram in (0x80001000, 0x80001010, 0x80002000, 0x80002008):
+            firstSym = firstContext.globalSegment.getSymbol(vram, tryPlusOffset=False)
+            secondSym = secondContext.globalSegment.getSymbol(vram, tryPlusOffset=False)
+            assert firstSym is not None and secondSym is not None
+            self.assertEqual(secondSym.size, firstSym.autodetectedSize)
+            self.assertTrue(secondSym.isUserDeclared)
+
+        secondOutput = self.disassemble(secondContext)
+        self.assertEqual(secondOutput, firstOutput)
+        self.assertEqual(self.exportJson(secondContext), exported)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,83 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+import rabbitizer
+
+from spimdisasm import mips
+
+from SpimdisasmTestCase import SpimdisasmTestCase
+
+
+class TestTailCalls(SpimdisasmTestCase):
+    "Unconditional `j` and `b` only end a function when they leave it for another function"
+
+    def analyzeText(self, words: list[int], knownFunctions: list[int]=[]) -> mips.sections.SectionText:
+        context = self.createContext()
+        for vram in knownFunctions:
+            context.globalSegment.addFunction(vram, isAutogenerated=True)
+        text = self.createText(context, words)
+        text.analyze()
+        return text
+
+    def test_tailCallJ(self) -> None:
+        words = [
+            # func_80000000
+            0x24020001, # addiu $v0, $zero, 1
+            0x08000006, # j func_80000018
+            0x00000000, # nop
+            # func_8000000C
+            0x24020002, # addiu $v0, $zero, 2
+            0x03E00008, # jr $ra
+            0x00000000, # nop
+            # func_80000018
+            0x03E00008, # jr $ra
+            0x00000000, # nop
+        ]
+        expected = [(0x80000000, 0x8000000C), (0x8000000C, 0x80000018), (0x80000018, 0x80000020)]
+
+        self.addCleanup(setattr, rabbitizer.config, "toolchainTweaks_treatJAsUnconditionalBranch", rabbitizer.config.toolchainTweaks_treatJAsUnconditionalBranch)
+        for treatJAsBranch in (False, True):
+            with self.subTest(treatJAsBranch=treatJAsBranch):
+                rabbitizer.config.toolchainTweaks_treatJAsUnconditionalBranch = treatJAsBranch
+                self.assertEqual(self.analyzeText(words, knownFunctions=[0x80000018]).getFunctionRanges(), expected)
+
+    def test_localForwardJ(self) -> None:
+        words = [
+            # func_80000000
+            0x24020001, # addiu $v0, $zero, 1
+            0x08000004, # j .L80000010
+            0x00000000, # nop
+            0x24020002, # addiu $v0, $zero, 2
+            # .L80000010
+            0x03E00008, # jr $ra
+            0x00000000, # nop
+        ]
+
+        text = self.analyzeText(words)
+        self.assertEqual(text.getFunctionRanges(), [(0x80000000, 0x80000018)])
+        # The target is a label of the function instead of a new function
+        self.assertEqual(self.getDirectives(text.disassemble())[1], "j .L80000010")
+
+    def test_localB(self) -> None:
+        words = [
+            # func_80000000
+            0x24020001, # addiu $v0, $zero, 1
+            0x10000002, # b .L80000010
+            0x00000000, # nop
+            0x24020002, # addiu $v0, $zero, 2
+            # .L80000010
+            0x03E00008, # jr $ra
+            0x00000000, # nop
+        ]
+
+        self.assertEqual(self.analyzeText(words).getFunctionRanges(), [(0x80000000, 0x80000018)])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,71 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from spimdisasm import common
+
+from SpimdisasmTestCase import SpimdisasmTestCase
+
+
+class TestTrailingPadding(SpimdisasmTestCase):
+    "The `nop`s placed after the return of a function to align the next one can be emitted as `.balign`"
+
+    def disassembleText(self, words: list[int]) -> list[str]:
+        context = self.createContext()
+        text = self.createText(context, words)
+        text.analyze()
+        return self.getDirectives(text.disassemble())
+
+    def test_padding(self) -> None:
+        common.GlobalConfig.ASM_TEXT_PADDING_AS_BALIGN = True
+
+        self.assertEqual(self.disassembleText([
+            0x03E00008, # jr $ra
+            0x24020001, # addiu $v0, $zero, 1
+            0x00000000, # nop
+            0x00000000, # nop
+        ]), [
+            "jr $ra",
+            "addiu $v0, $zero, 0x1",
+            ".balign 16",
+        ])
+
+    def test_delaySlotIsNotPadding(self) -> None:
+        common.GlobalConfig.ASM_TEXT_PADDING_AS_BALIGN = True
+
+        self.assertEqual(self.disassembleText([
+            0x24020001, # addiu $v0, $zero, 1
+            0x03E00008, # jr $ra
+            0x00000000, # nop
+            0x00000000, # nop
+        ]), [
+            "addiu $v0, $zero, 0x1",
+            "jr $ra",
+            "nop",
+            # The smallest alignment which produces the same amount of padding
+            ".balign 8",
+        ])
+
+    def test_disabled(self) -> None:
+        common.GlobalConfig.ASM_TEXT_PADDING_AS_BALIGN = False
+
+        self.assertEqual(self.disassembleText([
+            0x03E00008, # jr $ra
+            0x24020001, # addiu $v0, $zero, 1
+            0x00000000, # nop
+            0x00000000, # nop
+        ]), [
+            "jr $ra",
+            "addiu $v0, $zero, 0x1",
+            "nop",
+            "nop",
+        ])
+
+
+if __name__ == "__main__":
+    unittest.main()
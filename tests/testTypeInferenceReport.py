@@ -0,0 +1,42 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from SpimdisasmTestCase import SpimdisasmTestCase
+
+
+class TestTypeInferenceReport(SpimdisasmTestCase):
+    "The type guessed for each autogenerated symbol, with its evidence and confidence"
+
+    words = [
+        # func_80000000
+        0x3C018000, # lui $at, %hi(0x80001000)
+        0xC4201000, # lwc1 $f0, %lo(0x80001000)($at)
+        0x3C018000, # lui $at, %hi(0x80001008)
+        0x84221008, # lh $v0, %lo(0x80001008)($at)
+        0x3C018000, # lui $at, %hi(0x80001008)
+        0x80231008, # lb $v1, %lo(0x80001008)($at)
+        0x03E00008, # jr $ra
+        0x00000000, # nop
+    ]
+
+    def test_report(self) -> None:
+        context = self.createContext()
+        text = self.createText(context, self.words)
+        text.analyze()
+
+        report = [(inferred.vram, inferred.type, inferred.evidence, round(inferred.confidence, 2)) for inferred in context.getTypeInferenceReport()]
+        self.assertEqual(report, [
+            # Sorted by ascending confidence
+            (0x80001008, "s16", ["accessed as s16/s8", "aligned to 2", "referenced 2 times"], 0.4),
+            (0x80001000, "f32", ["accessed as f32", "aligned to 4", "referenced 1 times"], 1.0),
+        ])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,54 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from spimdisasm import common
+from spimdisasm import mips
+
+from SpimdisasmTestCase import SpimdisasmTestCase
+
+
+class TestUnalignedTargets(SpimdisasmTestCase):
+    "A function calling an unaligned address through a register is likely data decoded as code"
+
+    words = [
+        # func_80000000
+        0x3C198000, # lui $t9, %hi(0x80000102)
+        0x27390102, # addiu $t9, $t9, %lo(0x80000102)
+        0x0320F809, # jalr $t9
+        0x00000000, # nop
+        0x03E00008, # jr $ra
+        0x00000000, # nop
+    ]
+
+    def analyzeText(self) -> tuple[mips.sections.SectionText, str]:
+        context = self.createContext()
+        text = self.createText(context, self.words)
+        with self.captureWarnings() as warnings:
+            text.analyze()
+        return text, warnings.getvalue()
+
+    def test_diagnostic(self) -> None:
+        text, warnings = self.analyzeText()
+
+        self.assertEqual(warnings, "Warning: The instruction at 0x80000008 (function 'func_80000000') calls the unaligned address 0x80000102. This function may be data decoded as code\n")
+        func = text.symbolList[0]
+        assert isinstance(func, mips.symbols.SymbolFunction)
+        self.assertTrue(func.hasUnalignedTargets)
+        self.assertFalse(func.isEmittedAsData())
+
+    def test_asData(self) -> None:
+        common.GlobalConfig.UNALIGNED_TARGETS_AS_DATA = True
+        text, _ = self.analyzeText()
+
+        self.assertEqual(text.getCodeDataRanges(), [(0x80000000, 0x80000018, False)])
+        self.assertEqual(self.getDirectives(text.disassemble()), [f".word 0x{word:08X}" for word in self.words])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,79 @@
+#!/usr/bin/env python3
+
+# SPDX-FileCopyrightText: © 2022 Decompollaborate
+# SPDX-License-Identifier: MIT
+
+from __future__ import annotations
+
+import unittest
+
+from spimdisasm import common
+from spimdisasm import mips
+
+from SpimdisasmTestCase import SpimdisasmTestCase
+
+
+class TestUnterminatedFunctions(SpimdisasmTestCase):
+    "A section which ends in the middle of a function"
+
+    words = [
+        # func_80000000
+        0x03E00008, # jr $ra
+        0x00000000, # nop
+        # func_80000008, cut by the end of the section
+        0x24020001, # addiu $v0, $zero, 1
+        0x24420001, # addiu $v0, $v0, 1
+    ]
+
+    def analyzeText(self) -> tuple[mips.sections.SectionText, str]:
+        context = self.createContext()
+        text = self.createText(context, self.words)
+        with self.captureWarnings() as warnings:
+            text.analyze()
+        return text, warnings.getvalue()
+
+    def test_diagnostic(self) -> None:
+        text, warnings = self.analyzeText()
+
+        self.assertEqual(warnings, "Warning: The function at 0x80000008 (in 'test') does not end before the section does. The section's split point may be wrong.\n")
+
+        funcs = text.symbolList
+        self.assertEqual(len(funcs), 2)
+        assert isinstance(funcs[0], mips.symbols.SymbolFunction) and isinstance(funcs[1], mips.symbols.SymbolFunction)
+        self.assertFalse(funcs[0].isUnterminated)
+        self.assertTrue(funcs[1].isUnterminated)
+
+        output = text.disassemble()
+        self.assertEqual(output.count("# Warning: this function does not end before the section does"), 1)
+        self.assertLess(output.index("func_80000000"), output.index("# Warning"))
+
+    def test_sizedToSectionEnd(self) -> None:
+        text, _ = self.analyzeText()
+
+        self.assertEqual(text.getFunctionRanges(), [(0x80000000, 0x80000008), (0x80000008, 0x80000010)])
+        self.assertEqual(text.getCodeDataRanges(), [(0x80000000, 0x80000010, True)])
+
+    def test_asData(self) -> None:
+        common.GlobalConfig.UNTERMINATED_FUNCTIONS_AS_DATA = True
+        text, warnings = self.analyzeText()
+
+        self.assertIn("does not end before the section does", warnings)
+        self.assertEqual(text.getCodeDataRanges(), [(0x80000000, 0x80000008, True), (0x80000008, 0x80000010, False)])
+
+        output = text.disassemble()
+        self.assertIn(".word 0x24020001", output)
+        self.assertIn(".word 0x24420001", output)
+        self.assertNotIn("# Warning", output)
+
+    def test_terminatedSection(self) -> None:
+        context = self.createContext()
+        text = self.createText(context, self.words[:2])
+        with self.captureWarnings() as warnings:
+            text.analyze()
+
+        self.assertEqual(warnings.getvalue(), "")
+        self.assertEqual(text.getCodeDataRanges(), [(0x80000000, 0x80000008, True)])
+
+
+if __name__ == "__main__":
+    unittest.main()